@@ -1,10 +1,25 @@
+use crate::framework::{DownloadSource, PuzzlePart};
+
 #[derive(Debug)]
 pub enum Error {
     // Used by framework
     NoCachedInputAvailable,
     ExampleBlockNotFound(u8),
     MissingAdventOfCodeSessionId,
+    UnrecognizedSubmitResponse(String),
+    IncorrectAnswer {
+        source: DownloadSource,
+        part: PuzzlePart,
+        expected: String,
+        actual: String,
+    },
     NotYetImplemented,
+    NoMatchingPuzzle,
+    ParseFailed(Box<Error>),
+    SolveFailed {
+        part: PuzzlePart,
+        source: Box<Error>,
+    },
 
     WrappedError(Box<dyn std::error::Error>),
     ExpectedExactlyOne,
@@ -69,6 +84,21 @@ pub enum Error {
     NotFullySimplified(String),
 }
 
+impl Error {
+    /// Whether the error indicates that a puzzle part hasn't been
+    /// written yet, either directly or as the cause of a failed
+    /// solution.
+    pub fn is_not_yet_implemented(&self) -> bool {
+        match self {
+            Error::NotYetImplemented => true,
+            Error::SolveFailed { source, .. } => {
+                source.is_not_yet_implemented()
+            }
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Arg {
     String(String),
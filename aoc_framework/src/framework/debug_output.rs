@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::io::Write;
+
+thread_local! {
+    static DEBUG_OUTPUT: RefCell<Option<Box<dyn Write>>> =
+        RefCell::new(default_debug_output());
+}
+
+// Debug output is written to stdout if the `AOC_DEBUG` environment
+// variable is set, and is otherwise discarded.
+fn default_debug_output() -> Option<Box<dyn Write>> {
+    std::env::var_os("AOC_DEBUG")
+        .map(|_| Box::new(std::io::stdout()) as Box<dyn Write>)
+}
+
+/// Set the destination for `debug_println!` on the current thread,
+/// returning the previous destination.  If `None`, debug output is
+/// discarded.
+pub fn set_debug_output(
+    output: Option<Box<dyn Write>>,
+) -> Option<Box<dyn Write>> {
+    DEBUG_OUTPUT.with_borrow_mut(|current| std::mem::replace(current, output))
+}
+
+/// Whether `debug_println!` currently produces any output.  Can be
+/// used to skip expensive formatting that is only needed for debug
+/// output.
+pub fn debug_output_enabled() -> bool {
+    DEBUG_OUTPUT.with_borrow(|current| current.is_some())
+}
+
+#[doc(hidden)]
+pub fn write_debug_line(args: std::fmt::Arguments) {
+    DEBUG_OUTPUT.with_borrow_mut(|current| {
+        if let Some(output) = current {
+            // Failing to write debug output shouldn't interrupt the
+            // puzzle solution.
+            let _ = writeln!(output, "{args}");
+        }
+    })
+}
+
+/// Print a line of debug output, with the same syntax as `println!`.
+/// The output is discarded unless enabled, either by setting the
+/// `AOC_DEBUG` environment variable or by calling
+/// `set_debug_output`.
+#[macro_export]
+macro_rules! debug_println {
+    ($($arg:tt)*) => {
+        $crate::framework::write_debug_line(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_debug_println() {
+        let buffer = SharedBuffer::default();
+        let contents = || String::from_utf8(buffer.0.borrow().clone()).unwrap();
+
+        set_debug_output(None);
+        assert!(!debug_output_enabled());
+        debug_println!("hidden");
+
+        set_debug_output(Some(Box::new(buffer.clone())));
+        assert!(debug_output_enabled());
+        debug_println!("x = {}", 5);
+
+        set_debug_output(None);
+        debug_println!("also hidden");
+
+        assert_eq!(contents(), "x = 5\n");
+    }
+}
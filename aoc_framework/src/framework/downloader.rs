@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 
 use itertools::Itertools;
 
+use crate::framework::PuzzlePart;
 use crate::Error;
 
 use html5ever::tendril::TendrilSink;
@@ -15,6 +16,8 @@ pub struct Downloader {
     aoc_session_id: String,
     rate_limiter: Ratelimiter,
     cache: HashMap<DownloadTarget, String>,
+    cache_dir: PathBuf,
+    offline: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -23,6 +26,20 @@ pub enum DownloadSource {
     Example(usize),
 }
 
+/// The response from the Advent of Code server after submitting an
+/// answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect,
+    /// The previous submission was too recent, and must wait before
+    /// submitting again.
+    TooRecent,
+    /// The puzzle part has either already been solved, or is not yet
+    /// available.
+    AlreadyComplete,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct DownloadTarget {
     source: DownloadSource,
@@ -32,18 +49,60 @@ struct DownloadTarget {
 
 impl Downloader {
     pub fn new() -> Result<Downloader, Error> {
+        let aoc_session_id = std::env::var("AOC_SESSION_ID")
+            .map_err(|_| Error::MissingAdventOfCodeSessionId)?;
+        Ok(Self::with_session_id(aoc_session_id))
+    }
+
+    pub fn with_session_id(aoc_session_id: impl Into<String>) -> Downloader {
+        let aoc_session_id = aoc_session_id.into();
         // No more than one interaction every 5 seconds.
         let rate_limiter =
             Ratelimiter::builder(1, std::time::Duration::new(5, 0))
                 .build()
                 .unwrap();
-        let aoc_session_id = std::env::var("AOC_SESSION_ID")
-            .map_err(|_| Error::MissingAdventOfCodeSessionId)?;
-        Ok(Downloader {
+        let cache_dir = [".", ".cache", &aoc_session_id].iter().collect();
+        Downloader {
             rate_limiter,
             aoc_session_id,
             cache: HashMap::new(),
-        })
+            cache_dir,
+            offline: false,
+        }
+    }
+
+    /// A downloader that never reads from disk or makes network
+    /// requests.  Inputs must be provided with `with_static_input`.
+    pub fn offline() -> Downloader {
+        Downloader {
+            offline: true,
+            ..Self::with_session_id("")
+        }
+    }
+
+    /// Provide the input to be returned for the specified puzzle and
+    /// source, rather than downloading it.
+    pub fn with_static_input(
+        mut self,
+        year: u32,
+        day: u32,
+        source: DownloadSource,
+        input: impl Into<String>,
+    ) -> Downloader {
+        let target = DownloadTarget { source, year, day };
+        self.cache.insert(target, input.into());
+        self
+    }
+
+    /// Set the directory in which downloaded files are stored.  Any
+    /// file already present in the directory is used instead of
+    /// making a network request.  Defaults to
+    /// `./.cache/$AOC_SESSION_ID`.
+    pub fn with_cache_dir(self, cache_dir: impl Into<PathBuf>) -> Downloader {
+        Downloader {
+            cache_dir: cache_dir.into(),
+            ..self
+        }
     }
 
     pub fn puzzle_input(
@@ -52,6 +111,17 @@ impl Downloader {
         day: u32,
         input_source: DownloadSource,
     ) -> Result<impl Iterator<Item = &str>, Error> {
+        Ok(self.puzzle_input_text(year, day, input_source)?.lines())
+    }
+
+    /// The full text of the puzzle input, including any trailing
+    /// newlines.
+    pub fn puzzle_input_text(
+        &mut self,
+        year: u32,
+        day: u32,
+        input_source: DownloadSource,
+    ) -> Result<&str, Error> {
         let target = DownloadTarget {
             year,
             day,
@@ -60,10 +130,14 @@ impl Downloader {
         if !self.cache.contains_key(&target) {
             self.load_to_cache(target)?;
         }
-        Ok(self.cache[&target].lines())
+        Ok(&self.cache[&target])
     }
 
     fn load_to_cache(&mut self, target: DownloadTarget) -> Result<(), Error> {
+        if self.offline {
+            return Err(Error::NoCachedInputAvailable);
+        }
+
         let input_string = match target.source {
             DownloadSource::User => {
                 self.user_puzzle_input(target.year, target.day)
@@ -99,6 +173,36 @@ impl Downloader {
             .ok_or(Error::NoneError)
     }
 
+    /// Submit an answer for one part of a puzzle.  Unlike downloads,
+    /// submissions are never cached.
+    pub fn submit(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: PuzzlePart,
+        answer: &str,
+    ) -> Result<SubmitOutcome, Error> {
+        let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+        let body = format!(
+            "level={}&answer={}",
+            part.part_num(),
+            url_encode(answer.trim())
+        );
+
+        self.wait_for_rate_limit();
+        let client = reqwest::blocking::Client::new();
+        let response = client
+            .post(url)
+            .header("cookie", format!("session={}", self.aoc_session_id))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()?
+            .error_for_status()?
+            .text()?;
+
+        parse_submit_response(&response)
+    }
+
     fn find_example_blocks<P>(
         &self,
         path: P,
@@ -149,18 +253,15 @@ impl Downloader {
         }
     }
 
+    fn cache_path(&self, url: &str) -> PathBuf {
+        self.cache_dir.join(url.replace('/', "_"))
+    }
+
     fn cache_file_loc<U: reqwest::IntoUrl>(
         &mut self,
         url: U,
     ) -> Result<PathBuf, Error> {
-        let path: PathBuf = [
-            ".",
-            ".cache",
-            &self.aoc_session_id,
-            &url.as_str().replace('/', "_"),
-        ]
-        .iter()
-        .collect();
+        let path = self.cache_path(url.as_str());
 
         if !path.exists() {
             self.wait_for_rate_limit();
@@ -184,6 +285,54 @@ impl Downloader {
     }
 }
 
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn parse_submit_response(html: &str) -> Result<SubmitOutcome, Error> {
+    let dom = html5ever::parse_document(
+        markup5ever_rcdom::RcDom::default(),
+        Default::default(),
+    )
+    .one(html);
+
+    let message: String = dom
+        .document
+        .walk()
+        .filter(|handle| match handle.data {
+            NodeData::Element { ref name, .. } => {
+                name.local.to_string() == "article"
+            }
+            _ => false,
+        })
+        .flat_map(|handle| handle.walk())
+        .filter_map(|handle| match &handle.data {
+            NodeData::Text { contents } => Some(contents.borrow().to_string()),
+            _ => None,
+        })
+        .join("");
+
+    if message.contains("That's the right answer") {
+        Ok(SubmitOutcome::Correct)
+    } else if message.contains("That's not the right answer") {
+        Ok(SubmitOutcome::Incorrect)
+    } else if message.contains("You gave an answer too recently") {
+        Ok(SubmitOutcome::TooRecent)
+    } else if message.contains("You don't seem to be solving the right level") {
+        Ok(SubmitOutcome::AlreadyComplete)
+    } else {
+        Err(Error::UnrecognizedSubmitResponse(message))
+    }
+}
+
 trait GraphWalker {
     fn walk(&self) -> RcDomWalker;
 }
@@ -233,3 +382,94 @@ impl Iterator for RcDomWalker {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_from_cache_dir() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let mut downloader = Downloader::with_session_id("no-such-session")
+            .with_cache_dir(cache_dir.path());
+
+        let input_path =
+            downloader.cache_path("https://adventofcode.com/2000/day/1/input");
+        std::fs::write(input_path, "1\n2\n3\n").unwrap();
+
+        let page_path =
+            downloader.cache_path("https://adventofcode.com/2000/day/1");
+        std::fs::write(
+            page_path,
+            "<html><body>\
+             <pre><code>first\nexample</code></pre>\
+             <pre><code>second\nexample</code></pre>\
+             </body></html>",
+        )
+        .unwrap();
+
+        // Both the user input and the examples are read from disk.
+        // If a network request were attempted, it would fail, either
+        // from lack of a network or from the invalid session id.
+        let lines: Vec<String> = downloader
+            .puzzle_input(2000, 1, DownloadSource::User)
+            .unwrap()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(lines, vec!["1", "2", "3"]);
+
+        let lines: Vec<String> = downloader
+            .puzzle_input(2000, 1, DownloadSource::Example(1))
+            .unwrap()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(lines, vec!["second", "example"]);
+    }
+
+    #[test]
+    fn test_url_encode() {
+        assert_eq!(url_encode("abc-123"), "abc-123");
+        assert_eq!(url_encode("a b&c=d"), "a%20b%26c%3Dd");
+    }
+
+    #[test]
+    fn test_parse_submit_response() {
+        let wrap = |message: &str| {
+            format!(
+                "<html><body><main><article><p>{message}</p></article>\
+                 </main></body></html>"
+            )
+        };
+
+        let outcomes = [
+            (
+                "That's the right answer!  You are one gold star closer.",
+                SubmitOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitOutcome::Incorrect,
+            ),
+            (
+                "You gave an answer too recently; you have to wait.",
+                SubmitOutcome::TooRecent,
+            ),
+            (
+                "You don't seem to be solving the right level.  \
+                 Did you already complete it?",
+                SubmitOutcome::AlreadyComplete,
+            ),
+        ];
+        for (message, expected) in outcomes {
+            assert_eq!(
+                parse_submit_response(&wrap(message)).unwrap(),
+                expected
+            );
+        }
+
+        assert!(matches!(
+            parse_submit_response(&wrap("Something else entirely")),
+            Err(Error::UnrecognizedSubmitResponse(_))
+        ));
+    }
+}
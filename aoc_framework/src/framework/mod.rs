@@ -1,8 +1,17 @@
+mod debug_output;
+pub use crate::debug_println;
+pub use debug_output::{
+    debug_output_enabled, set_debug_output, write_debug_line,
+};
+
 mod downloader;
-pub use downloader::{DownloadSource, Downloader};
+pub use downloader::{DownloadSource, Downloader, SubmitOutcome};
 
 mod puzzle;
 pub use puzzle::{
-    Puzzle, PuzzleInputSource, PuzzlePart, PuzzleRunner, PuzzleRunnerImpl,
-    YearDay,
+    answers_match, ExampleSpec, PartStatus, Puzzle, PuzzleAnswer, PuzzleDate,
+    PuzzleInputSource, PuzzlePart, PuzzleRunner, PuzzleRunnerImpl, YearDay,
 };
+
+mod run_args;
+pub use run_args::{run_from_args, RunFilter};
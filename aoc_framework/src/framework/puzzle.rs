@@ -1,10 +1,12 @@
 use super::{DownloadSource, Downloader};
 use crate::Error;
+use itertools::Either;
 
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PuzzlePart {
     Part1,
     Part2,
@@ -31,16 +33,124 @@ impl Display for PuzzlePart {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+/// The result of running one part of a puzzle, where a part that
+/// returns `Error::NotYetImplemented` is skipped rather than failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved(String),
+    Skipped,
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            PartStatus::Solved(output) => write!(f, "{output}"),
+            PartStatus::Skipped => write!(f, "Skipped, not yet implemented"),
+        }
+    }
+}
+
+/// The date of a puzzle.  Ordered by year, then by day, and
+/// displayed as `"2021 day 05"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleDate {
+    pub year: u32,
+    pub day: u8,
+}
+
+impl Display for PuzzleDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.pad(&format!("{:04} day {:02}", self.year, self.day))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum PuzzleInputSource {
     User,
     Example,
 }
 
+/// The output produced by one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleAnswer {
+    pub part: PuzzlePart,
+    pub output: String,
+}
+
+impl Display for PuzzleAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.output)
+    }
+}
+
+/// An example input for a puzzle, given as the index of the example
+/// block in the puzzle description, along with the expected answers
+/// for that example.  Expected answers are compared against the
+/// `Debug` output of the puzzle part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExampleSpec {
+    pub example_num: u8,
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+impl ExampleSpec {
+    pub const fn new(example_num: u8) -> Self {
+        Self {
+            example_num,
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    pub const fn expect_part_1(self, expected: &'static str) -> Self {
+        Self {
+            part_1: Some(expected),
+            ..self
+        }
+    }
+
+    pub const fn expect_part_2(self, expected: &'static str) -> Self {
+        Self {
+            part_2: Some(expected),
+            ..self
+        }
+    }
+
+    pub fn expected(&self, part: PuzzlePart) -> Option<&'static str> {
+        match part {
+            PuzzlePart::Part1 => self.part_1,
+            PuzzlePart::Part2 => self.part_2,
+        }
+    }
+}
+
+/// Compare a puzzle's output against an expected answer, ignoring
+/// trailing whitespace on each line and any trailing newlines.
+pub fn answers_match(actual: &str, expected: &str) -> bool {
+    fn normalize(text: &str) -> impl Iterator<Item = &str> {
+        text.trim_end_matches('\n')
+            .lines()
+            .map(|line| line.trim_end())
+    }
+    normalize(actual).eq(normalize(expected))
+}
+
 pub trait PuzzleRunner {
     fn year(&self) -> u32;
     fn day(&self) -> u8;
 
+    fn date(&self) -> PuzzleDate {
+        PuzzleDate {
+            year: self.year(),
+            day: self.day(),
+        }
+    }
+
+    // The input source to run against.  Uses the example input if
+    // `use_example` is set, or if the puzzle prefers the example.
+    fn input_source(&self, use_example: bool) -> PuzzleInputSource;
+
     // Download and parse the results
     fn parse_inputs(
         &mut self,
@@ -58,6 +168,55 @@ pub trait PuzzleRunner {
         puzzle_part: PuzzlePart,
         input_source: PuzzleInputSource,
     ) -> Result<String, Error>;
+
+    // Run the puzzle as in `run_puzzle_part`, but report a part that
+    // hasn't been implemented yet as `PartStatus::Skipped` instead of
+    // as an error.
+    fn run_puzzle_part_status(
+        &self,
+        puzzle_part: PuzzlePart,
+        input_source: PuzzleInputSource,
+    ) -> Result<PartStatus, Error> {
+        match self.run_puzzle_part(puzzle_part, input_source) {
+            Ok(output) => Ok(PartStatus::Solved(output)),
+            Err(err) if err.is_not_yet_implemented() => Ok(PartStatus::Skipped),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Parse the provided input once, then run both parts of the
+    // puzzle against it.  Does not use or modify the cached inputs.
+    // If parsing fails, both parts return `Error::ParseFailed`.  Part
+    // 1 holds the original error, and part 2 holds its description.
+    fn solve_both(
+        &self,
+        input: &str,
+    ) -> (Result<PuzzleAnswer, Error>, Result<PuzzleAnswer, Error>);
+
+    // Run each example declared by the puzzle, and check the output
+    // of each part against the expected answers.  Returns an error
+    // for the first example that fails, either while running or due
+    // to an incorrect answer.
+    fn verify_examples(&self, downloader: &mut Downloader)
+        -> Result<(), Error>;
+
+    // Parse the provided input and run both parts of the puzzle,
+    // without using the cached inputs.  Returns the time spent
+    // parsing, the time spent solving both parts, and the output of
+    // each part.
+    fn run_timed(
+        &self,
+        input: &str,
+    ) -> Result<(Duration, Duration, String, String), Error>;
+
+    // Whether the puzzle declares known-correct answers for the user
+    // input.
+    fn has_known_answers(&self) -> bool;
+
+    // Run the puzzle against the user input, and check the output of
+    // each part against the known-correct answers.  Parts without a
+    // known answer are not run.
+    fn verify(&self, downloader: &mut Downloader) -> Result<(), Error>;
 }
 
 pub struct PuzzleRunnerImpl<T: Puzzle> {
@@ -75,6 +234,82 @@ where
     }
 }
 
+impl<T> PuzzleRunnerImpl<T>
+where
+    T: Puzzle,
+{
+    // Split the input text into lines.  If `Puzzle::TRIM_INPUT` is
+    // set, a single trailing newline is removed first, so that an
+    // input ending in a blank line is still accepted.  Otherwise,
+    // the input is split on every newline, so a trailing newline
+    // produces a final empty line.
+    fn input_lines(text: &str) -> impl Iterator<Item = &str> {
+        if T::TRIM_INPUT {
+            let text = text
+                .strip_suffix('\n')
+                .map(|text| text.strip_suffix('\r').unwrap_or(text))
+                .unwrap_or(text);
+            Either::Left(text.lines())
+        } else {
+            Either::Right(
+                text.split('\n')
+                    .map(|line| line.strip_suffix('\r').unwrap_or(line)),
+            )
+        }
+    }
+
+    // Parse the input, wrapping any failure as `Error::ParseFailed`
+    // to distinguish it from errors raised while solving.
+    fn parse<'a>(
+        lines: impl Iterator<Item = &'a str>,
+    ) -> Result<T::ParsedInput, Error> {
+        T::parse_input(lines).map_err(|err| Error::ParseFailed(Box::new(err)))
+    }
+
+    // Run one part of the puzzle, wrapping any failure as
+    // `Error::SolveFailed` for that part.
+    fn run_parsed(
+        input: &T::ParsedInput,
+        puzzle_part: PuzzlePart,
+    ) -> Result<String, Error> {
+        let output = match puzzle_part {
+            PuzzlePart::Part1 => T::part_1(input).map(|out| format!("{out:?}")),
+            PuzzlePart::Part2 => T::part_2(input).map(|out| format!("{out:?}")),
+        };
+        output.map_err(|err| Error::SolveFailed {
+            part: puzzle_part,
+            source: Box::new(err),
+        })
+    }
+
+    fn check_answers(
+        downloader: &mut Downloader,
+        source: DownloadSource,
+        expected: impl Fn(PuzzlePart) -> Option<&'static str>,
+    ) -> Result<(), Error> {
+        let text =
+            downloader.puzzle_input_text(T::year(), T::day() as u32, source)?;
+        let parsed = Self::parse(Self::input_lines(text))?;
+
+        PuzzlePart::iter().try_for_each(|part| {
+            let Some(expected) = expected(part) else {
+                return Ok(());
+            };
+            let actual = Self::run_parsed(&parsed, part)?;
+            if answers_match(&actual, expected) {
+                Ok(())
+            } else {
+                Err(Error::IncorrectAnswer {
+                    source,
+                    part,
+                    expected: expected.to_string(),
+                    actual,
+                })
+            }
+        })
+    }
+}
+
 impl<T> PuzzleRunner for PuzzleRunnerImpl<T>
 where
     T: Puzzle,
@@ -87,6 +322,14 @@ where
         T::day()
     }
 
+    fn input_source(&self, use_example: bool) -> PuzzleInputSource {
+        if use_example || T::prefers_example() {
+            PuzzleInputSource::Example
+        } else {
+            PuzzleInputSource::User
+        }
+    }
+
     fn parse_inputs(
         &mut self,
         downloader: &mut Downloader,
@@ -99,17 +342,18 @@ where
                 DownloadSource::Example(T::EXAMPLE_NUM as usize)
             }
         };
-        let line_iter = downloader.puzzle_input(
+        let text = downloader.puzzle_input_text(
             T::year(),
             T::day() as u32,
             download_source,
         )?;
+        let line_iter = Self::input_lines(text);
         let parsed_input = if verbose {
-            T::parse_input(
+            Self::parse(
                 line_iter.inspect(|line| println!("Parsing line {line}")),
             )
         } else {
-            T::parse_input(line_iter)
+            Self::parse(line_iter)
         }?;
 
         self.input_cache.insert(input_source, parsed_input);
@@ -127,21 +371,119 @@ where
             .get(&input_source)
             .ok_or(Error::NoCachedInputAvailable)?;
 
-        Ok(match puzzle_part {
-            PuzzlePart::Part1 => format!("{:?}", T::part_1(input)?),
-            PuzzlePart::Part2 => format!("{:?}", T::part_2(input)?),
+        Self::run_parsed(input, puzzle_part)
+    }
+
+    fn solve_both(
+        &self,
+        input: &str,
+    ) -> (Result<PuzzleAnswer, Error>, Result<PuzzleAnswer, Error>) {
+        let parsed = match Self::parse(Self::input_lines(input)) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                let description = match &err {
+                    Error::ParseFailed(inner) => format!("{inner:?}"),
+                    other => format!("{other:?}"),
+                };
+                let copy = Error::ParseFailed(Box::new(Error::InvalidString(
+                    description,
+                )));
+                return (Err(err), Err(copy));
+            }
+        };
+
+        let solve = |part: PuzzlePart| {
+            Self::run_parsed(&parsed, part)
+                .map(|output| PuzzleAnswer { part, output })
+        };
+
+        (solve(PuzzlePart::Part1), solve(PuzzlePart::Part2))
+    }
+
+    fn verify_examples(
+        &self,
+        downloader: &mut Downloader,
+    ) -> Result<(), Error> {
+        T::examples().into_iter().try_for_each(|example| {
+            let source = DownloadSource::Example(example.example_num as usize);
+            Self::check_answers(downloader, source, |part| {
+                example.expected(part)
+            })
         })
     }
+
+    fn run_timed(
+        &self,
+        input: &str,
+    ) -> Result<(Duration, Duration, String, String), Error> {
+        let start = Instant::now();
+        let parsed = Self::parse(Self::input_lines(input))?;
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        let part_1 = Self::run_parsed(&parsed, PuzzlePart::Part1)?;
+        let part_2 = Self::run_parsed(&parsed, PuzzlePart::Part2)?;
+        let solve_time = start.elapsed();
+
+        Ok((parse_time, solve_time, part_1, part_2))
+    }
+
+    fn has_known_answers(&self) -> bool {
+        T::PART_1_ANSWER.is_some() || T::PART_2_ANSWER.is_some()
+    }
+
+    fn verify(&self, downloader: &mut Downloader) -> Result<(), Error> {
+        if !self.has_known_answers() {
+            return Ok(());
+        }
+        let expected = |part| match part {
+            PuzzlePart::Part1 => T::PART_1_ANSWER,
+            PuzzlePart::Part2 => T::PART_2_ANSWER,
+        };
+        Self::check_answers(downloader, DownloadSource::User, expected)
+    }
 }
 
 pub trait YearDay {
     fn year() -> u32;
     fn day() -> u8;
+
+    fn date() -> PuzzleDate {
+        PuzzleDate {
+            year: Self::year(),
+            day: Self::day(),
+        }
+    }
 }
 
 pub trait Puzzle: YearDay {
     const EXAMPLE_NUM: u8;
 
+    // Known-correct answers for the user input, used by
+    // `PuzzleRunner::verify` to catch regressions.  Compared against
+    // the `Debug` output of each part.
+    const PART_1_ANSWER: Option<&'static str> = None;
+    const PART_2_ANSWER: Option<&'static str> = None;
+
+    // All examples that should be checked by
+    // `PuzzleRunner::verify_examples`.  By default, only checks that
+    // the example given by `EXAMPLE_NUM` runs without error.
+    fn examples() -> Vec<ExampleSpec> {
+        vec![ExampleSpec::new(Self::EXAMPLE_NUM)]
+    }
+
+    // Whether a single trailing newline should be stripped from the
+    // input before it is split into lines.  If false, an input ending
+    // in a newline is passed to `parse_input` with a final empty line.
+    const TRIM_INPUT: bool = true;
+
+    // Whether the puzzle should run against the example input by
+    // default, rather than the user input.  Useful during
+    // development, without needing to pass `--example` on each run.
+    fn prefers_example() -> bool {
+        false
+    }
+
     type ParsedInput;
     fn parse_input<'a>(
         lines: impl Iterator<Item = &'a str>,
@@ -155,3 +497,430 @@ pub trait Puzzle: YearDay {
         parsed: &Self::ParsedInput,
     ) -> Result<impl std::fmt::Debug, Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SumAndProduct;
+
+    impl YearDay for SumAndProduct {
+        fn year() -> u32 {
+            2000
+        }
+        fn day() -> u8 {
+            1
+        }
+    }
+
+    impl Puzzle for SumAndProduct {
+        const EXAMPLE_NUM: u8 = 0;
+
+        fn examples() -> Vec<ExampleSpec> {
+            vec![
+                ExampleSpec::new(0).expect_part_1("11").expect_part_2("30"),
+                ExampleSpec::new(2).expect_part_2("-12"),
+            ]
+        }
+
+        type ParsedInput = Vec<i64>;
+        fn parse_input<'a>(
+            lines: impl Iterator<Item = &'a str>,
+        ) -> Result<Self::ParsedInput, Error> {
+            Ok(lines.map(|line| line.parse()).collect::<Result<_, _>>()?)
+        }
+
+        fn part_1(
+            values: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Ok(values.iter().sum::<i64>())
+        }
+
+        fn part_2(
+            values: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Ok(values.iter().product::<i64>())
+        }
+    }
+
+    #[test]
+    fn test_puzzle_date() {
+        let date = |year, day| PuzzleDate { year, day };
+        let mut dates = vec![
+            date(2022, 3),
+            date(2015, 25),
+            date(2021, 5),
+            date(2022, 1),
+            date(2015, 1),
+            date(2021, 12),
+        ];
+        dates.sort();
+        assert_eq!(
+            dates,
+            vec![
+                date(2015, 1),
+                date(2015, 25),
+                date(2021, 5),
+                date(2021, 12),
+                date(2022, 1),
+                date(2022, 3),
+            ]
+        );
+
+        assert_eq!(date(2021, 5).to_string(), "2021 day 05");
+        assert_eq!(format!("{:<13}|", date(2021, 5)), "2021 day 05  |");
+        assert_eq!(SumAndProduct::date(), date(2000, 1));
+        assert_eq!(
+            PuzzleRunnerImpl::<KnownAnswers>::new_box().date(),
+            date(2000, 2)
+        );
+    }
+
+    #[test]
+    fn test_answers_match() {
+        assert!(answers_match("42", "42"));
+        assert!(answers_match("#..#  \n.##.\n", "#..#\n.##.   "));
+        assert!(answers_match("abc\n\n", "abc"));
+        assert!(!answers_match("abc", " abc"));
+        assert!(!answers_match("a\nb", "a\n\nb"));
+        assert!(!answers_match("42", "43"));
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        let (part_1, part_2) = runner.solve_both("2\n3\n4");
+
+        assert_eq!(
+            part_1.unwrap(),
+            PuzzleAnswer {
+                part: PuzzlePart::Part1,
+                output: "9".to_string()
+            }
+        );
+        assert_eq!(
+            part_2.unwrap(),
+            PuzzleAnswer {
+                part: PuzzlePart::Part2,
+                output: "24".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_run_timed() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        let (_, _, part_1, part_2) = runner.run_timed("2\n3\n4").unwrap();
+        assert_eq!(part_1, "9");
+        assert_eq!(part_2, "24");
+
+        assert!(runner.run_timed("2\nthree").is_err());
+    }
+
+    #[test]
+    fn test_solve_both_parse_error() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        let (part_1, part_2) = runner.solve_both("2\nthree\n4");
+
+        let Err(Error::ParseFailed(err)) = part_1 else {
+            panic!("Expected parse failure, found {part_1:?}");
+        };
+        assert!(matches!(*err, Error::WrappedError(_)));
+
+        let Err(Error::ParseFailed(err_2)) = part_2 else {
+            panic!("Expected parse failure, found {part_2:?}");
+        };
+        let Error::InvalidString(description) = *err_2 else {
+            panic!("Expected description of parse failure");
+        };
+        assert_eq!(description, format!("{err:?}"));
+    }
+
+    #[test]
+    fn test_run_with_static_input() {
+        let mut downloader = Downloader::offline()
+            .with_static_input(2000, 1, DownloadSource::User, "2\n3\n4")
+            .with_static_input(2000, 1, DownloadSource::Example(0), "5\n6");
+        let mut runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+
+        runner
+            .parse_inputs(&mut downloader, PuzzleInputSource::User, false)
+            .unwrap();
+        runner
+            .parse_inputs(&mut downloader, PuzzleInputSource::Example, false)
+            .unwrap();
+
+        let run = |part, source| runner.run_puzzle_part(part, source).unwrap();
+        assert_eq!(run(PuzzlePart::Part1, PuzzleInputSource::User), "9");
+        assert_eq!(run(PuzzlePart::Part2, PuzzleInputSource::User), "24");
+        assert_eq!(run(PuzzlePart::Part1, PuzzleInputSource::Example), "11");
+        assert_eq!(run(PuzzlePart::Part2, PuzzleInputSource::Example), "30");
+    }
+
+    #[test]
+    fn test_offline_missing_input() {
+        let mut downloader = Downloader::offline();
+        let mut runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        assert!(matches!(
+            runner.parse_inputs(
+                &mut downloader,
+                PuzzleInputSource::User,
+                false
+            ),
+            Err(Error::NoCachedInputAvailable)
+        ));
+    }
+
+    struct KnownAnswers;
+
+    impl YearDay for KnownAnswers {
+        fn year() -> u32 {
+            2000
+        }
+        fn day() -> u8 {
+            2
+        }
+    }
+
+    impl Puzzle for KnownAnswers {
+        const EXAMPLE_NUM: u8 = 0;
+        const PART_1_ANSWER: Option<&'static str> = Some("\"abc\"");
+
+        type ParsedInput = String;
+        fn parse_input<'a>(
+            mut lines: impl Iterator<Item = &'a str>,
+        ) -> Result<Self::ParsedInput, Error> {
+            Ok(lines.next().ok_or(Error::NoneError)?.to_string())
+        }
+
+        fn part_1(
+            parsed: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Ok(parsed.clone())
+        }
+
+        fn part_2(
+            _: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Err::<(), _>(Error::NotYetImplemented)
+        }
+    }
+
+    struct InDevelopment;
+
+    impl YearDay for InDevelopment {
+        fn year() -> u32 {
+            2000
+        }
+        fn day() -> u8 {
+            3
+        }
+    }
+
+    impl Puzzle for InDevelopment {
+        const EXAMPLE_NUM: u8 = 0;
+
+        fn prefers_example() -> bool {
+            true
+        }
+
+        type ParsedInput = ();
+        fn parse_input<'a>(
+            _: impl Iterator<Item = &'a str>,
+        ) -> Result<Self::ParsedInput, Error> {
+            Ok(())
+        }
+
+        fn part_1(
+            _: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Err::<(), _>(Error::NotYetImplemented)
+        }
+
+        fn part_2(
+            _: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Err::<(), _>(Error::NotYetImplemented)
+        }
+    }
+
+    struct CountLines;
+
+    impl YearDay for CountLines {
+        fn year() -> u32 {
+            2000
+        }
+        fn day() -> u8 {
+            4
+        }
+    }
+
+    impl Puzzle for CountLines {
+        const EXAMPLE_NUM: u8 = 0;
+        const TRIM_INPUT: bool = false;
+
+        type ParsedInput = Vec<String>;
+        fn parse_input<'a>(
+            lines: impl Iterator<Item = &'a str>,
+        ) -> Result<Self::ParsedInput, Error> {
+            Ok(lines.map(|line| line.to_string()).collect())
+        }
+
+        fn part_1(
+            lines: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Ok(lines.len())
+        }
+
+        fn part_2(
+            lines: &Self::ParsedInput,
+        ) -> Result<impl std::fmt::Debug, Error> {
+            Ok(lines.iter().filter(|line| line.is_empty()).count())
+        }
+    }
+
+    #[test]
+    fn test_trim_input() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        for input in ["2\n3\n4", "2\n3\n4\n", "2\n3\n4\n\n", "2\r\n3\r\n4\r\n"]
+        {
+            let (_, _, part_1, part_2) = runner.run_timed(input).unwrap();
+            assert_eq!(part_1, "9");
+            assert_eq!(part_2, "24");
+        }
+        assert!(runner.run_timed("2\n3\n4\n\n\n").is_err());
+    }
+
+    #[test]
+    fn test_untrimmed_input() {
+        let runner = PuzzleRunnerImpl::<CountLines>::new_box();
+        let run = |input| {
+            let (_, _, part_1, part_2) = runner.run_timed(input).unwrap();
+            (part_1, part_2)
+        };
+        assert_eq!(run("a\nb"), ("2".to_string(), "0".to_string()));
+        assert_eq!(run("a\nb\n"), ("3".to_string(), "1".to_string()));
+        assert_eq!(run("a\n\nb\n\n"), ("5".to_string(), "3".to_string()));
+
+        let untrimmed = PuzzleRunnerImpl::<CountLines>::input_lines("a\nb\n");
+        assert_eq!(untrimmed.count(), 3);
+        let trimmed = PuzzleRunnerImpl::<SumAndProduct>::input_lines("a\nb\n");
+        assert_eq!(trimmed.count(), 2);
+    }
+
+    #[test]
+    fn test_input_source() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        assert_eq!(runner.input_source(false), PuzzleInputSource::User);
+        assert_eq!(runner.input_source(true), PuzzleInputSource::Example);
+
+        let runner = PuzzleRunnerImpl::<InDevelopment>::new_box();
+        assert_eq!(runner.input_source(false), PuzzleInputSource::Example);
+        assert_eq!(runner.input_source(true), PuzzleInputSource::Example);
+    }
+
+    #[test]
+    fn test_verify() {
+        let runner = PuzzleRunnerImpl::<KnownAnswers>::new_box();
+        assert!(runner.has_known_answers());
+
+        let mut downloader = Downloader::offline().with_static_input(
+            2000,
+            2,
+            DownloadSource::User,
+            "abc",
+        );
+        runner.verify(&mut downloader).unwrap();
+
+        let mut downloader = Downloader::offline().with_static_input(
+            2000,
+            2,
+            DownloadSource::User,
+            "abd",
+        );
+        assert!(matches!(
+            runner.verify(&mut downloader),
+            Err(Error::IncorrectAnswer {
+                source: DownloadSource::User,
+                part: PuzzlePart::Part1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_solve_failure_identifies_part() {
+        let runner = PuzzleRunnerImpl::<KnownAnswers>::new_box();
+        let (part_1, part_2) = runner.solve_both("abc");
+
+        assert_eq!(part_1.unwrap().output, "\"abc\"");
+        let Err(Error::SolveFailed { part, source }) = part_2 else {
+            panic!("Expected solve failure, found {part_2:?}");
+        };
+        assert_eq!(part, PuzzlePart::Part2);
+        assert!(matches!(*source, Error::NotYetImplemented));
+    }
+
+    #[test]
+    fn test_skip_unimplemented_part() {
+        let mut downloader = Downloader::offline().with_static_input(
+            2000,
+            2,
+            DownloadSource::User,
+            "abc",
+        );
+        let mut runner = PuzzleRunnerImpl::<KnownAnswers>::new_box();
+        runner
+            .parse_inputs(&mut downloader, PuzzleInputSource::User, false)
+            .unwrap();
+
+        let status = |part| {
+            runner
+                .run_puzzle_part_status(part, PuzzleInputSource::User)
+                .unwrap()
+        };
+        assert_eq!(
+            status(PuzzlePart::Part1),
+            PartStatus::Solved("\"abc\"".to_string())
+        );
+        assert_eq!(status(PuzzlePart::Part2), PartStatus::Skipped);
+    }
+
+    #[test]
+    fn test_verify_without_known_answers() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+        assert!(!runner.has_known_answers());
+
+        // No input is required if there is nothing to check.
+        let mut downloader = Downloader::offline();
+        runner.verify(&mut downloader).unwrap();
+    }
+
+    #[test]
+    fn test_verify_examples() {
+        let runner = PuzzleRunnerImpl::<SumAndProduct>::new_box();
+
+        let mut downloader = Downloader::offline()
+            .with_static_input(2000, 1, DownloadSource::Example(0), "5\n6")
+            .with_static_input(2000, 1, DownloadSource::Example(2), "3\n-4");
+        runner.verify_examples(&mut downloader).unwrap();
+
+        let mut downloader = Downloader::offline()
+            .with_static_input(2000, 1, DownloadSource::Example(0), "5\n6")
+            .with_static_input(2000, 1, DownloadSource::Example(2), "3\n4");
+        match runner.verify_examples(&mut downloader) {
+            Err(Error::IncorrectAnswer {
+                source,
+                part,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(source, DownloadSource::Example(2));
+                assert_eq!(part, PuzzlePart::Part2);
+                assert_eq!(expected, "-12");
+                assert_eq!(actual, "12");
+            }
+            other => panic!("Expected incorrect answer, found {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use itertools::Itertools;
+
+use super::{
+    set_debug_output, DownloadSource, Downloader, PuzzleDate, PuzzlePart,
+    PuzzleRunner,
+};
+use crate::errors::Arg;
+use crate::Error;
+
+/// Selection of puzzles and parts to run, along with how they should
+/// be run, as parsed from command-line flags by
+/// `RunFilter::from_args`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunFilter {
+    pub year: Option<u32>,
+    pub day: Option<u8>,
+    pub part: Option<PuzzlePart>,
+    pub use_example: bool,
+    pub all: bool,
+    pub verbose: bool,
+    pub benchmark_iter: Option<u32>,
+    pub verify_examples: bool,
+    pub time_all: bool,
+}
+
+impl RunFilter {
+    /// Parse the flags `--year <N>`, `--day <N>`, `--part <1|2>`,
+    /// `--example`, `--all`, `--verbose`, and `--benchmark-iter <N>`,
+    /// along with the single-letter forms `-y`, `-d`, `-p`, `-e`,
+    /// `-a`, `-v`, and `-b`, and the long-only flags
+    /// `--verify-examples` and `--time-all`.  The arguments should
+    /// not include the program name.
+    pub fn from_args(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Self, Error> {
+        let mut filter = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| Error::InvalidArg(flag.as_str().into()))
+            };
+            match flag.as_str() {
+                "-y" | "--year" => filter.year = Some(value()?.parse()?),
+                "-d" | "--day" => filter.day = Some(value()?.parse()?),
+                "-p" | "--part" => {
+                    filter.part = match value()?.as_str() {
+                        "1" => Some(PuzzlePart::Part1),
+                        "2" => Some(PuzzlePart::Part2),
+                        other => return Err(Error::InvalidArg(other.into())),
+                    }
+                }
+                "-e" | "--example" => filter.use_example = true,
+                "-a" | "--all" => filter.all = true,
+                "-v" | "--verbose" => filter.verbose = true,
+                "-b" | "--benchmark-iter" => {
+                    filter.benchmark_iter = Some(value()?.parse()?)
+                }
+                "--verify-examples" => filter.verify_examples = true,
+                "--time-all" => filter.time_all = true,
+                _ => return Err(Error::InvalidArg(Arg::String(flag))),
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Select which of the available puzzles should be run.  With
+    /// `--all`, every puzzle matching the year and day (if given) is
+    /// selected, in chronological order.  Otherwise, a single puzzle
+    /// is selected, defaulting to the most recent year and to the
+    /// most recent day within that year.
+    pub fn select(
+        &self,
+        available: impl IntoIterator<Item = PuzzleDate>,
+    ) -> Vec<PuzzleDate> {
+        let mut matching: Vec<PuzzleDate> = available
+            .into_iter()
+            .filter(|date| self.year.is_none_or(|year| date.year == year))
+            .filter(|date| self.day.is_none_or(|day| date.day == day))
+            .collect();
+        matching.sort();
+        matching.dedup();
+
+        if self.all {
+            return matching;
+        }
+
+        let year = self
+            .year
+            .or_else(|| matching.iter().map(|date| date.year).max());
+        matching
+            .into_iter()
+            .filter(|date| Some(date.year) == year)
+            .max()
+            .into_iter()
+            .collect()
+    }
+
+    /// The parts to run for each selected puzzle.
+    pub fn parts(&self) -> Vec<PuzzlePart> {
+        match self.part {
+            Some(part) => vec![part],
+            None => PuzzlePart::iter().collect(),
+        }
+    }
+}
+
+/// Time both parts of every puzzle on the user input, printing a
+/// table sorted from slowest to fastest.  Puzzles that fail, including
+/// those whose input cannot be downloaded, are listed afterwards.
+fn time_all_solutions(
+    runners: &[Box<dyn PuzzleRunner>],
+    downloader: &mut Downloader,
+) {
+    let timings: Vec<_> = runners
+        .iter()
+        .map(|runner| {
+            let res = downloader
+                .puzzle_input_text(
+                    runner.year(),
+                    runner.day() as u32,
+                    DownloadSource::User,
+                )
+                .map(|input| input.to_string())
+                .and_then(|input| runner.run_timed(&input));
+            (runner.date(), res)
+        })
+        .collect();
+
+    let (successes, failures): (Vec<_>, Vec<_>) =
+        timings.into_iter().partition(|(_, res)| res.is_ok());
+
+    println!("{:<11}  {:>12}  {:>12}", "Puzzle", "Parse", "Solve");
+    successes
+        .into_iter()
+        .filter_map(|(date, res)| {
+            let (parse_time, solve_time, _, _) = res.ok()?;
+            Some((date, parse_time, solve_time))
+        })
+        .sorted_by_key(|&(date, parse_time, solve_time)| {
+            (std::cmp::Reverse(parse_time + solve_time), date)
+        })
+        .for_each(|(date, parse_time, solve_time)| {
+            println!(
+                "{date:<11}  {:>12}  {:>12}",
+                format!("{parse_time:.2?}"),
+                format!("{solve_time:.2?}")
+            );
+        });
+
+    failures
+        .into_iter()
+        .sorted_by_key(|(date, _)| *date)
+        .for_each(|(date, res)| {
+            if let Err(err) = res {
+                println!("{date:<11}  Error: {err:?}");
+            }
+        });
+}
+
+/// Parse command-line arguments as in `RunFilter::from_args`, then
+/// run each selected puzzle from `runners`, printing the output of
+/// each part.  Since the puzzle solutions are collected in crates
+/// that depend on this one, the caller provides the runners.
+pub fn run_from_args(
+    runners: Vec<Box<dyn PuzzleRunner>>,
+    args: impl IntoIterator<Item = String>,
+) -> Result<(), Error> {
+    let filter = RunFilter::from_args(args)?;
+
+    if filter.verbose {
+        set_debug_output(Some(Box::new(std::io::stdout())));
+    }
+
+    if filter.time_all {
+        time_all_solutions(&runners, &mut Downloader::new()?);
+        return Ok(());
+    }
+
+    let selected = filter.select(runners.iter().map(|runner| runner.date()));
+    if selected.is_empty() {
+        return Err(Error::NoMatchingPuzzle);
+    }
+
+    let mut downloader = Downloader::new()?;
+    let mut runners: Vec<_> = runners
+        .into_iter()
+        .filter(|runner| selected.contains(&runner.date()))
+        .collect();
+    runners.sort_by_key(|runner| runner.date());
+
+    runners.iter_mut().try_for_each(|runner| {
+        if filter.verify_examples {
+            runner.verify_examples(&mut downloader)?;
+            println!("{}, all examples correct", runner.date());
+            return Ok(());
+        }
+
+        let input_source = runner.input_source(filter.use_example);
+        runner.parse_inputs(&mut downloader, input_source, filter.verbose)?;
+        filter.parts().into_iter().try_for_each(|part| {
+            println!("{}, {}", runner.date(), part);
+            let iterations = filter.benchmark_iter.unwrap_or(1).max(1);
+            let start = std::time::Instant::now();
+            let status = (0..iterations)
+                .map(|_| runner.run_puzzle_part_status(part, input_source))
+                .last()
+                .unwrap();
+            if filter.benchmark_iter.is_some() {
+                println!("Avg. runtime: {:?}", start.elapsed() / iterations);
+            }
+            match status {
+                Ok(status) => {
+                    println!("{status}");
+                    Ok(())
+                }
+                Err(err) => {
+                    println!("Error: {err:?}");
+                    Err(err)
+                }
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<RunFilter, Error> {
+        RunFilter::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_year_day() {
+        assert_eq!(
+            parse(&["--year", "2021", "--day", "5"]).unwrap(),
+            RunFilter {
+                year: Some(2021),
+                day: Some(5),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        assert_eq!(
+            parse(&["-e", "--part", "2", "-a", "-y", "2015"]).unwrap(),
+            RunFilter {
+                year: Some(2015),
+                day: None,
+                part: Some(PuzzlePart::Part2),
+                use_example: true,
+                all: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            parse(&["-v", "--benchmark-iter", "10", "--verify-examples"])
+                .unwrap(),
+            RunFilter {
+                verbose: true,
+                benchmark_iter: Some(10),
+                verify_examples: true,
+                ..Default::default()
+            }
+        );
+        assert!(parse(&["--time-all"]).unwrap().time_all);
+        assert_eq!(parse(&[]).unwrap(), RunFilter::default());
+
+        assert!(matches!(parse(&["--day"]), Err(Error::InvalidArg(_))));
+        assert!(matches!(parse(&["--part", "3"]), Err(Error::InvalidArg(_))));
+        assert!(matches!(
+            parse(&["--example-input"]),
+            Err(Error::InvalidArg(_))
+        ));
+        assert!(parse(&["--day", "five"]).is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let date = |year, day| PuzzleDate { year, day };
+        let available =
+            [date(2021, 5), date(2015, 3), date(2021, 12), date(2022, 1)];
+        let select = |args: &[&str]| parse(args).unwrap().select(available);
+
+        assert_eq!(select(&[]), vec![date(2022, 1)]);
+        assert_eq!(select(&["--year", "2021"]), vec![date(2021, 12)]);
+        assert_eq!(select(&["--day", "5"]), vec![date(2021, 5)]);
+        assert_eq!(
+            select(&["--year", "2021", "--all"]),
+            vec![date(2021, 5), date(2021, 12)]
+        );
+        assert_eq!(select(&["--all"]).len(), 4);
+        assert_eq!(select(&["--year", "2000"]), vec![]);
+    }
+}
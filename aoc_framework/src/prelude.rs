@@ -1 +1,3 @@
+pub use crate::debug_println;
+pub use crate::framework::ExampleSpec;
 pub use crate::{Error, Puzzle};
@@ -0,0 +1,28 @@
+use aoc_framework::framework::{Downloader, PuzzleRunner};
+
+// Requires the AOC_SESSION_ID environment variable, in order to
+// download user inputs.  Run with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn verify_known_answers() {
+    let runners: Vec<Box<dyn PuzzleRunner>> = std::iter::empty()
+        .chain(aoc_year2015::solutions())
+        .chain(aoc_year2021::solutions())
+        .chain(aoc_year2022::solutions())
+        .chain(aoc_year2023::solutions())
+        .filter(|runner| runner.has_known_answers())
+        .collect();
+
+    let mut downloader = Downloader::new().unwrap();
+
+    let failures: Vec<String> = runners
+        .iter()
+        .filter_map(|runner| {
+            runner.verify(&mut downloader).err().map(|err| {
+                format!("{:04}-12-{:02}: {err:?}", runner.year(), runner.day())
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "Incorrect answers: {failures:#?}");
+}
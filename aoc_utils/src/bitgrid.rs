@@ -0,0 +1,202 @@
+use crate::GridMap;
+
+/// A dense 2-d grid of booleans, packed one bit per cell.  Each row
+/// occupies a whole number of `u64` words, with bit `x % 64` of word
+/// `x / 64` holding column `x`.  Any padding bits past `x_size` are
+/// kept at zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    x_size: usize,
+    y_size: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        let words_per_row = x_size.div_ceil(64);
+        Self {
+            x_size,
+            y_size,
+            words_per_row,
+            words: vec![0; words_per_row * y_size],
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.x_size, self.y_size)
+    }
+
+    pub fn is_valid(&self, x: usize, y: usize) -> bool {
+        x < self.x_size && y < self.y_size
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        assert!(self.is_valid(x, y));
+        let word = self.words[y * self.words_per_row + x / 64];
+        (word >> (x % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(self.is_valid(x, y));
+        let word = &mut self.words[y * self.words_per_row + x / 64];
+        let mask = 1u64 << (x % 64);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        let start = y * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Mask of the valid bits within the last word of each row.
+    fn last_word_mask(&self) -> u64 {
+        match self.x_size % 64 {
+            0 => !0,
+            rem => (1u64 << rem) - 1,
+        }
+    }
+
+    /// Advance one generation of Conway's Game of Life, treating
+    /// cells outside the grid as dead.  Neighbor counts are computed
+    /// 64 cells at a time, as a bit-sliced counter that saturates
+    /// once four neighbors have been seen.
+    pub fn step_life(&self) -> Self {
+        let mut next = Self::new(self.x_size, self.y_size);
+        let empty_row = vec![0; self.words_per_row];
+        let last_word_mask = self.last_word_mask();
+
+        for y in 0..self.y_size {
+            let above = if y > 0 { self.row(y - 1) } else { &empty_row };
+            let center = self.row(y);
+            let below = if y + 1 < self.y_size {
+                self.row(y + 1)
+            } else {
+                &empty_row
+            };
+
+            for i in 0..self.words_per_row {
+                let prev = |row: &[u64]| if i > 0 { row[i - 1] } else { 0 };
+                let following =
+                    |row: &[u64]| row.get(i + 1).copied().unwrap_or(0);
+
+                // Shifted so that bit x holds the cell at x-1 / x+1.
+                let from_left = |row: &[u64]| (row[i] << 1) | (prev(row) >> 63);
+                let from_right =
+                    |row: &[u64]| (row[i] >> 1) | (following(row) << 63);
+
+                let neighbors = [
+                    from_left(above),
+                    above[i],
+                    from_right(above),
+                    from_left(center),
+                    from_right(center),
+                    from_left(below),
+                    below[i],
+                    from_right(below),
+                ];
+
+                let (mut ones, mut twos, mut fours) = (0u64, 0u64, 0u64);
+                for mask in neighbors {
+                    let carry = ones & mask;
+                    ones ^= mask;
+                    fours |= twos & carry;
+                    twos ^= carry;
+                }
+
+                let cell = center[i];
+                let mut alive = !fours & twos & (ones | cell);
+                if i + 1 == self.words_per_row {
+                    alive &= last_word_mask;
+                }
+                next.words[y * self.words_per_row + i] = alive;
+            }
+        }
+
+        next
+    }
+}
+
+impl From<&GridMap<bool>> for BitGrid {
+    fn from(map: &GridMap<bool>) -> Self {
+        let (x_size, y_size) = map.shape();
+        let mut grid = Self::new(x_size, y_size);
+        map.iter::<((i64, i64), &bool)>()
+            .filter(|(_, &value)| value)
+            .for_each(|((x, y), _)| grid.set(x as usize, y as usize, true));
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Adjacency;
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = BitGrid::new(100, 3);
+        assert_eq!(grid.count_ones(), 0);
+        grid.set(0, 0, true);
+        grid.set(63, 1, true);
+        grid.set(64, 1, true);
+        grid.set(99, 2, true);
+        assert!(grid.get(0, 0));
+        assert!(grid.get(63, 1));
+        assert!(grid.get(64, 1));
+        assert!(grid.get(99, 2));
+        assert!(!grid.get(64, 0));
+        assert_eq!(grid.count_ones(), 4);
+
+        grid.set(63, 1, false);
+        assert!(!grid.get(63, 1));
+        assert_eq!(grid.count_ones(), 3);
+    }
+
+    #[test]
+    fn test_step_life_matches_gridmap() {
+        let life_step = |map: &GridMap<bool>| -> GridMap<bool> {
+            map.map_neighborhood(Adjacency::Queen, false, |&cell, neighbors| {
+                let num_alive = neighbors.iter().filter(|&&b| b).count();
+                matches!((cell, num_alive), (true, 2 | 3) | (false, 3))
+            })
+        };
+
+        // Wide enough to exercise neighbors that cross word
+        // boundaries, and to have a partially-filled final word.
+        let (x_size, y_size) = (130, 20);
+        let mut state: u64 = 12345;
+        let mut reference = GridMap::new_uniform(x_size, y_size, false);
+        for y in 0..y_size as i64 {
+            for x in 0..x_size as i64 {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                reference[(x, y)] = (state >> 61) < 3;
+            }
+        }
+
+        let mut grid = BitGrid::from(&reference);
+        assert_eq!(
+            grid.count_ones(),
+            reference.iter_item().filter(|&&b| b).count()
+        );
+
+        for _ in 0..5 {
+            reference = life_step(&reference);
+            grid = grid.step_life();
+            assert_eq!(grid, BitGrid::from(&reference));
+        }
+    }
+}
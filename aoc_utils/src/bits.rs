@@ -0,0 +1,54 @@
+/// Iterate over every submask of `mask`, including `mask` itself and
+/// zero.  Submasks are produced in decreasing numeric order.
+pub fn iter_subsets(mask: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(mask), move |&sub| {
+        (sub != 0).then(|| (sub - 1) & mask)
+    })
+}
+
+/// Iterate over the indices of the set bits in `mask`, from least
+/// significant to most significant.
+pub fn iter_set_bits(mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::successors((mask != 0).then_some(mask), |&remaining| {
+        let remaining = remaining & (remaining - 1);
+        (remaining != 0).then_some(remaining)
+    })
+    .map(|remaining| remaining.trailing_zeros() as usize)
+}
+
+/// The number of set bits in `mask`.
+pub fn popcount(mask: u64) -> usize {
+    mask.count_ones() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_subsets() {
+        let subsets: Vec<u64> = iter_subsets(0b1011).collect();
+        assert_eq!(
+            subsets,
+            vec![0b1011, 0b1010, 0b1001, 0b1000, 0b0011, 0b0010, 0b0001, 0]
+        );
+        assert_eq!(iter_subsets(0b10101).count(), 8);
+        assert_eq!(iter_subsets(0).collect::<Vec<_>>(), vec![0]);
+        assert!(iter_subsets(0b110110).all(|sub| sub & !0b110110 == 0));
+    }
+
+    #[test]
+    fn test_iter_set_bits() {
+        let bits: Vec<usize> = iter_set_bits(0b1001_0110).collect();
+        assert_eq!(bits, vec![1, 2, 4, 7]);
+        assert_eq!(iter_set_bits(0).count(), 0);
+        assert_eq!(iter_set_bits(1 << 63).collect::<Vec<_>>(), vec![63]);
+    }
+
+    #[test]
+    fn test_popcount() {
+        assert_eq!(popcount(0), 0);
+        assert_eq!(popcount(0b1011), 3);
+        assert_eq!(popcount(u64::MAX), 64);
+    }
+}
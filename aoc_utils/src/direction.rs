@@ -1,5 +1,7 @@
 use crate::prelude::Vector;
 
+/// A cardinal direction on a grid, as displayed.  `Up` is north and
+/// `Right` is east.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
@@ -9,7 +11,22 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn as_vec(self) -> Vector<2, i64> {
+    /// Parse a direction from any of the common notations: "UDLR",
+    /// "NSEW", or arrows "^v<>".
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Direction::Up),
+            'D' | 'S' | 'v' => Some(Direction::Down),
+            'L' | 'W' | '<' => Some(Direction::Left),
+            'R' | 'E' | '>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// The unit step in this direction.  Uses the same convention as
+    /// `GridMap`, with x increasing to the right and y increasing
+    /// downward, so `Up` is (0,-1).
+    pub fn to_vector(self) -> Vector<2, i64> {
         match self {
             Direction::Up => [0, -1].into(),
             Direction::Down => [0, 1].into(),
@@ -18,7 +35,7 @@ impl Direction {
         }
     }
 
-    pub fn iter_cardinal() -> impl Iterator<Item = Direction> {
+    pub fn iter_all() -> impl Iterator<Item = Direction> {
         [Self::Up, Self::Down, Self::Left, Self::Right].into_iter()
     }
 
@@ -49,3 +66,147 @@ impl Direction {
         }
     }
 }
+
+/// A direction on a grid, including diagonals, as displayed.  The
+/// variants are listed in clockwise order, starting from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    const CLOCKWISE: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn iter_all() -> impl Iterator<Item = Direction8> {
+        Self::CLOCKWISE.into_iter()
+    }
+
+    /// The step in this direction, using the same convention as
+    /// `Direction::to_vector`.  Diagonal steps change both x and y.
+    pub fn to_vector(self) -> Vector<2, i64> {
+        match self {
+            Direction8::Up => [0, -1].into(),
+            Direction8::UpRight => [1, -1].into(),
+            Direction8::Right => [1, 0].into(),
+            Direction8::DownRight => [1, 1].into(),
+            Direction8::Down => [0, 1].into(),
+            Direction8::DownLeft => [-1, 1].into(),
+            Direction8::Left => [-1, 0].into(),
+            Direction8::UpLeft => [-1, -1].into(),
+        }
+    }
+
+    /// The direction of a single step, if the vector is a step to
+    /// one of the eight neighboring positions.
+    pub fn from_vector(vec: Vector<2, i64>) -> Option<Self> {
+        Self::iter_all().find(|dir| dir.to_vector() == vec)
+    }
+
+    /// Rotate by 45 degrees per step.  Positive steps rotate
+    /// clockwise, and negative steps rotate counter-clockwise.
+    pub fn rotate_45(self, steps: i8) -> Self {
+        let index = (self as i64 + steps as i64).rem_euclid(8);
+        Self::CLOCKWISE[index as usize]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        (self as u8) % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Up.reverse(), Direction::Down);
+        for dir in Direction::iter_all() {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+        }
+    }
+
+    #[test]
+    fn test_to_vector() {
+        assert_eq!(Direction::Up.to_vector(), [0, -1].into());
+        assert_eq!(Direction::Right.to_vector(), [1, 0].into());
+        for dir in Direction::iter_all() {
+            assert_eq!(
+                dir.to_vector() + dir.reverse().to_vector(),
+                Vector::zero()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_char() {
+        assert_eq!(Direction::from_char('N'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('L'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('E'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn test_direction8_vector_round_trip() {
+        for dir in Direction8::iter_all() {
+            assert_eq!(Direction8::from_vector(dir.to_vector()), Some(dir));
+        }
+        assert_eq!(Direction8::from_vector([0, 0].into()), None);
+        assert_eq!(Direction8::from_vector([2, 0].into()), None);
+        assert_eq!(Direction8::iter_all().count(), 8);
+    }
+
+    #[test]
+    fn test_direction8_rotate() {
+        assert_eq!(Direction8::Up.rotate_45(1), Direction8::UpRight);
+        assert_eq!(Direction8::Up.rotate_45(-1), Direction8::UpLeft);
+        assert_eq!(Direction8::Left.rotate_45(3), Direction8::UpRight);
+        assert_eq!(Direction8::Down.rotate_45(-12), Direction8::Up);
+        for dir in Direction8::iter_all() {
+            assert_eq!(dir.rotate_45(8), dir);
+            assert_eq!(dir.rotate_45(4).to_vector(), -dir.to_vector());
+        }
+    }
+
+    #[test]
+    fn test_direction8_from_cardinal() {
+        for dir in Direction::iter_all() {
+            let dir8: Direction8 = dir.into();
+            assert!(!dir8.is_diagonal());
+            assert_eq!(dir8.to_vector(), dir.to_vector());
+            assert_eq!(dir8.rotate_45(2), dir.turn_right().into());
+        }
+        assert!(Direction8::DownLeft.is_diagonal());
+    }
+}
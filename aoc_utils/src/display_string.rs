@@ -14,3 +14,54 @@ impl From<String> for DisplayString {
         Self(str)
     }
 }
+
+/// Render rows of cells as a bracketed table, as used by the
+/// `Matrix` and `LinearSystem` displays.  Each column is padded to
+/// the width of its widest cell.  Rows may have differing numbers of
+/// cells, with missing cells rendered as blank.
+pub fn render_table(rows: &[Vec<String>]) -> String {
+    let num_cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let col_widths: Vec<usize> = (0..num_cols)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let total_width: usize = col_widths.iter().map(|w| w + 2).sum();
+
+    let mut out = String::new();
+    out.push_str(&format!("┌{:total_width$}┐\n", ""));
+    rows.iter().for_each(|row| {
+        out.push('|');
+        col_widths.iter().enumerate().for_each(|(i, width)| {
+            let cell = row.get(i).map(|s| s.as_str()).unwrap_or("");
+            out.push_str(&format!(" {cell:width$} "));
+        });
+        out.push_str("|\n");
+    });
+    out.push_str(&format!("└{:total_width$}┘\n", ""));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            vec!["a".to_string(), "bbb".to_string(), "cc".to_string()],
+            vec!["dddd".to_string(), "e".to_string(), "f".to_string()],
+        ];
+        let expected = "\
+┌               ┐
+| a     bbb  cc |
+| dddd  e    f  |
+└               ┘
+";
+        assert_eq!(render_table(&rows), expected);
+    }
+}
@@ -0,0 +1,69 @@
+pub trait DedupByKey: Iterator {
+    /// Collapse each run of consecutive items that share the same key
+    /// down to the first item of the run.  Items with equal keys that
+    /// are not adjacent are kept, as with `Vec::dedup_by_key`.
+    fn dedup_by_key<K, F>(self, key: F) -> DedupByKeyIter<Self, K, F>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: Fn(&Self::Item) -> K,
+    {
+        DedupByKeyIter {
+            iter: self,
+            key,
+            prev_key: None,
+        }
+    }
+}
+
+impl<T> DedupByKey for T where T: Iterator {}
+
+pub struct DedupByKeyIter<I, K, F> {
+    iter: I,
+    key: F,
+    prev_key: Option<K>,
+}
+
+impl<I, K, F> Iterator for DedupByKeyIter<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: Fn(&I::Item) -> K,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let key = (self.key)(&item);
+            if self.prev_key.as_ref() != Some(&key) {
+                self.prev_key = Some(key);
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_by_identity() {
+        let deduped: Vec<_> = [1, 1, 2, 2, 2, 1]
+            .into_iter()
+            .dedup_by_key(|&x| x)
+            .collect();
+        assert_eq!(deduped, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_dedup_keeps_first_of_run() {
+        let words = ["apple", "avocado", "banana", "blueberry", "cherry"];
+        let deduped: Vec<_> = words
+            .into_iter()
+            .dedup_by_key(|word| word.chars().next())
+            .collect();
+        assert_eq!(deduped, vec!["apple", "banana", "cherry"]);
+        assert_eq!(std::iter::empty::<i32>().dedup_by_key(|&x| x).count(), 0);
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub trait FoldGrouped: Iterator {
+    /// Group the items by key, folding the items of each group into a
+    /// single value.  Each group's accumulator starts as `init()`.
+    /// Unlike `Itertools::into_group_map`, no intermediate `Vec` is
+    /// collected for each group.
+    fn fold_grouped<K, V>(
+        self,
+        key: impl Fn(&Self::Item) -> K,
+        init: impl Fn() -> V,
+        fold: impl Fn(V, Self::Item) -> V,
+    ) -> HashMap<K, V>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        let mut groups = HashMap::new();
+        self.for_each(|item| {
+            let group = key(&item);
+            let acc = groups.remove(&group).unwrap_or_else(&init);
+            groups.insert(group, fold(acc, item));
+        });
+        groups
+    }
+}
+
+impl<Iter: Iterator> FoldGrouped for Iter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_grouped() {
+        let sums = [("a", 1), ("b", 10), ("a", 2), ("c", 100), ("b", 20)]
+            .into_iter()
+            .fold_grouped(|(key, _)| *key, || 0, |acc, (_, value)| acc + value);
+
+        let expected: HashMap<&str, i32> =
+            [("a", 3), ("b", 30), ("c", 100)].into_iter().collect();
+        assert_eq!(sums, expected);
+    }
+
+    #[test]
+    fn test_fold_grouped_by_parity() {
+        let groups = (1..=10).fold_grouped(
+            |value| value % 2 == 0,
+            Vec::new,
+            |mut acc, value| {
+                acc.push(value);
+                acc
+            },
+        );
+        assert_eq!(groups[&true], vec![2, 4, 6, 8, 10]);
+        assert_eq!(groups[&false], vec![1, 3, 5, 7, 9]);
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{Display, Write};
+
+pub trait JoinWith: Iterator {
+    /// Concatenate the `Display` output of each item, with `sep`
+    /// between adjacent items.
+    fn join_with(self, sep: char) -> String
+    where
+        Self: Sized,
+        Self::Item: Display,
+    {
+        let mut output = String::new();
+        for (i, item) in self.enumerate() {
+            if i > 0 {
+                output.push(sep);
+            }
+            write!(output, "{item}").unwrap();
+        }
+        output
+    }
+
+    /// Concatenate the `Display` output of each item, one item per
+    /// line.  The result has no trailing newline.
+    fn collect_lines(self) -> String
+    where
+        Self: Sized,
+        Self::Item: Display,
+    {
+        self.join_with('\n')
+    }
+}
+
+impl<Iter: Iterator> JoinWith for Iter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_with() {
+        assert_eq!("abc".chars().join_with(','), "a,b,c");
+        assert_eq!([1, 22, 333].into_iter().join_with(' '), "1 22 333");
+        assert_eq!(std::iter::empty::<char>().join_with(','), "");
+    }
+
+    #[test]
+    fn test_collect_lines() {
+        let rows = ["#..", ".#.", "..#"];
+        assert_eq!(rows.iter().collect_lines(), "#..\n.#.\n..#");
+        assert_eq!(["single"].iter().collect_lines(), "single");
+    }
+}
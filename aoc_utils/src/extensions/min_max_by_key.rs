@@ -0,0 +1,41 @@
+use itertools::{Itertools, MinMaxResult};
+
+pub trait MinMaxByKey: Iterator {
+    /// Find both the minimum and maximum items, as determined by the
+    /// key, in a single pass.  As with `Iterator::min_by_key` and
+    /// `Iterator::max_by_key`, ties are broken by returning the first
+    /// minimum and the last maximum.  Returns `None` for an empty
+    /// iterator.
+    fn min_max_by_key<K: Ord>(
+        self,
+        key: impl Fn(&Self::Item) -> K,
+    ) -> Option<(Self::Item, Self::Item)>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        match self.minmax_by_key(key) {
+            MinMaxResult::NoElements => None,
+            MinMaxResult::OneElement(item) => Some((item.clone(), item)),
+            MinMaxResult::MinMax(min, max) => Some((min, max)),
+        }
+    }
+}
+
+impl<Iter: Iterator> MinMaxByKey for Iter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_max_by_key() {
+        let words = ["apple", "fig", "banana", "kiwi", "cherry"];
+        assert_eq!(
+            words.iter().min_max_by_key(|word| word.len()),
+            Some((&"fig", &"cherry"))
+        );
+        assert_eq!([5].into_iter().min_max_by_key(|&x: &i32| -x), Some((5, 5)));
+        assert_eq!(std::iter::empty::<i32>().min_max_by_key(|&x| x), None);
+    }
+}
@@ -12,3 +12,18 @@ pub use exactly_one::*;
 
 mod char_iter_loc;
 pub use char_iter_loc::*;
+
+mod min_max_by_key;
+pub use min_max_by_key::*;
+
+mod range_merge;
+pub use range_merge::*;
+
+mod fold_grouped;
+pub use fold_grouped::*;
+
+mod dedup_by_key;
+pub use dedup_by_key::*;
+
+mod join_with;
+pub use join_with::*;
@@ -0,0 +1,51 @@
+pub trait RangeMerge: Iterator<Item = (i64, i64)> {
+    /// Merge inclusive integer ranges `(start, end)` into a sorted
+    /// list of non-overlapping ranges covering the same values.
+    /// Ranges that overlap or are directly adjacent, such as `(1,3)`
+    /// and `(4,5)`, are combined.  Empty ranges, with `start > end`,
+    /// are dropped.
+    fn merge_ranges(self) -> impl Iterator<Item = (i64, i64)>
+    where
+        Self: Sized,
+    {
+        let mut ranges: Vec<_> =
+            self.filter(|(start, end)| start <= end).collect();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(i64, i64)> = Vec::new();
+        ranges
+            .into_iter()
+            .for_each(|(start, end)| match merged.last_mut() {
+                Some((_, prev_end)) if start <= prev_end.saturating_add(1) => {
+                    *prev_end = (*prev_end).max(end);
+                }
+                _ => merged.push((start, end)),
+            });
+        merged.into_iter()
+    }
+}
+
+impl<Iter> RangeMerge for Iter where Iter: Iterator<Item = (i64, i64)> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        let merged: Vec<_> = [(1, 3), (2, 5), (8, 10)]
+            .into_iter()
+            .merge_ranges()
+            .collect();
+        assert_eq!(merged, vec![(1, 5), (8, 10)]);
+    }
+
+    #[test]
+    fn test_merge_ranges_unsorted_and_adjacent() {
+        let merged: Vec<_> = [(8, 10), (4, 5), (1, 3), (2, 2), (7, 6)]
+            .into_iter()
+            .merge_ranges()
+            .collect();
+        assert_eq!(merged, vec![(1, 5), (8, 10)]);
+    }
+}
@@ -1,9 +1,18 @@
 use std::fmt::Display;
-use std::iter::Sum;
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
 use num::integer::gcd as find_gcd;
 
+/// An exact rational number.
+///
+/// Arithmetic operators return a fraction in lowest terms with a
+/// positive denominator.  Common factors are cancelled before
+/// multiplying where possible, but the intermediate products may
+/// still overflow `T`.  As with the underlying integer operations,
+/// this panics in debug builds and wraps in release builds.
+/// Comparisons cross-multiply without reducing, and have the same
+/// overflow behavior.
 #[derive(Debug, Clone, Copy)]
 pub struct Fraction<T = i64> {
     pub num: T,
@@ -40,6 +49,165 @@ impl<T> Fraction<T> {
         Self { num, denom }
     }
 
+    /// Restrict the fraction to the inclusive range `[lo, hi]`.
+    /// Unlike `Ord::clamp`, the bounds may be given either as
+    /// fractions or as integers.  Panics if `lo > hi`.
+    pub fn clamp(
+        self,
+        lo: impl Into<Fraction<T>>,
+        hi: impl Into<Fraction<T>>,
+    ) -> Self
+    where
+        T: Copy,
+        T: Ord,
+        T: Mul<Output = T>,
+        T: num::Zero,
+    {
+        Ord::clamp(self, lo.into(), hi.into())
+    }
+
+    /// Approximate a floating-point value as a fraction, by
+    /// truncating its continued-fraction expansion before the
+    /// denominator would exceed `max_denom`.  Returns `None` if the
+    /// value is NaN or infinite, if its integer part cannot be
+    /// represented in `T`, or if `max_denom` is less than one.
+    pub fn from_f64_approx(value: f64, max_denom: T) -> Option<Self>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::FromPrimitive,
+        T: num::CheckedAdd + num::CheckedMul,
+    {
+        if !value.is_finite() || max_denom < T::one() {
+            return None;
+        }
+
+        // Each convergent h/k is generated from the previous two
+        // convergents.
+        let (mut h_prev, mut h) = (T::zero(), T::one());
+        let (mut k_prev, mut k) = (T::one(), T::zero());
+
+        let mut remainder = value;
+        for _ in 0..64 {
+            let whole = remainder.floor();
+            let Some(term) = T::from_f64(whole) else {
+                break;
+            };
+            let next_convergent = term
+                .checked_mul(&h)
+                .and_then(|x| x.checked_add(&h_prev))
+                .zip(term.checked_mul(&k).and_then(|x| x.checked_add(&k_prev)));
+            let Some((h_next, k_next)) = next_convergent else {
+                break;
+            };
+            if k_next > max_denom {
+                break;
+            }
+
+            (h_prev, h) = (h, h_next);
+            (k_prev, k) = (k, k_next);
+
+            let frac = remainder - whole;
+            if frac < 1e-12 {
+                break;
+            }
+            remainder = frac.recip();
+        }
+
+        // No convergent was accepted, leaving the initial 1/0.
+        if k.is_zero() {
+            return None;
+        }
+        Some(Self::new(h, k).normalize())
+    }
+
+    pub fn to_f64(&self) -> f64
+    where
+        T: num::ToPrimitive,
+    {
+        let num = self.num.to_f64().unwrap_or(f64::NAN);
+        let denom = self.denom.to_f64().unwrap_or(f64::NAN);
+        num / denom
+    }
+
+    /// Display the fraction as a decimal, with the specified number
+    /// of digits after the decimal point.
+    pub fn display_decimal(&self, places: usize) -> impl Display + '_
+    where
+        T: num::ToPrimitive,
+    {
+        DecimalDisplay {
+            fraction: self,
+            places,
+        }
+    }
+
+    /// Add two fractions, returning None if the result cannot be
+    /// represented without overflowing `T`.
+    pub fn checked_add(self, rhs: Self) -> Option<Self>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::CheckedAdd + num::CheckedMul,
+    {
+        let gcd: T = find_gcd(self.denom, rhs.denom);
+        let num = self
+            .num
+            .checked_mul(&(rhs.denom / gcd))?
+            .checked_add(&rhs.num.checked_mul(&(self.denom / gcd))?)?;
+        let denom = self.denom.checked_mul(&(rhs.denom / gcd))?;
+        Some(Self { num, denom }.normalize())
+    }
+
+    /// Subtract two fractions, returning None if the result cannot be
+    /// represented without overflowing `T`.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::CheckedSub + num::CheckedMul,
+    {
+        let gcd: T = find_gcd(self.denom, rhs.denom);
+        let num = self
+            .num
+            .checked_mul(&(rhs.denom / gcd))?
+            .checked_sub(&rhs.num.checked_mul(&(self.denom / gcd))?)?;
+        let denom = self.denom.checked_mul(&(rhs.denom / gcd))?;
+        Some(Self { num, denom }.normalize())
+    }
+
+    /// Multiply two fractions, returning None if the result cannot be
+    /// represented without overflowing `T`.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::CheckedMul,
+    {
+        let gcd_a = find_gcd(self.num, rhs.denom);
+        let gcd_b = find_gcd(rhs.num, self.denom);
+        let num = (self.num / gcd_a).checked_mul(&(rhs.num / gcd_b))?;
+        let denom = (self.denom / gcd_b).checked_mul(&(rhs.denom / gcd_a))?;
+        Some(Self { num, denom }.normalize())
+    }
+
+    /// Divide two fractions, returning None if the divisor is zero or
+    /// if the result cannot be represented without overflowing `T`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::CheckedMul,
+    {
+        if rhs.num.is_zero() {
+            return None;
+        }
+        self.checked_mul(Self {
+            num: rhs.denom,
+            denom: rhs.num,
+        })
+    }
+
     pub fn round_nearest(self) -> T
     where
         T: num::traits::Euclid,
@@ -76,7 +244,21 @@ where
 
 impl Into<f64> for Fraction {
     fn into(self) -> f64 {
-        (self.num as f64) / (self.denom as f64)
+        self.to_f64()
+    }
+}
+
+struct DecimalDisplay<'a, T> {
+    fraction: &'a Fraction<T>,
+    places: usize,
+}
+
+impl<T> Display for DecimalDisplay<'_, T>
+where
+    T: num::ToPrimitive,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.*}", self.places, self.fraction.to_f64())
     }
 }
 
@@ -160,6 +342,25 @@ where
     }
 }
 
+impl<T> PartialOrd<T> for Fraction<T>
+where
+    T: Copy,
+    T: Ord,
+    T: Mul<Output = T>,
+    T: num::Zero,
+{
+    fn partial_cmp(&self, other: &T) -> Option<std::cmp::Ordering> {
+        // a/b < c
+        // a < b*c (reverse based on sign of b)
+        let ordering = self.num.cmp(&(self.denom * *other));
+        if self.denom < T::zero() {
+            Some(ordering.reverse())
+        } else {
+            Some(ordering)
+        }
+    }
+}
+
 impl<T> Add for Fraction<T>
 where
     T: num::Integer,
@@ -241,8 +442,10 @@ where
     type Output = Fraction<T>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let num = self.num * rhs.num;
-        let denom = self.denom * rhs.denom;
+        let gcd_a = find_gcd(self.num, rhs.denom);
+        let gcd_b = find_gcd(rhs.num, self.denom);
+        let num = (self.num / gcd_a) * (rhs.num / gcd_b);
+        let denom = (self.denom / gcd_b) * (rhs.denom / gcd_a);
         Self { num, denom }.normalize()
     }
 }
@@ -255,25 +458,46 @@ where
     type Output = Fraction<T>;
 
     fn mul(self, rhs: T) -> Self::Output {
-        let num = self.num * rhs;
-        let denom = self.denom;
+        let gcd = find_gcd(rhs, self.denom);
+        let num = self.num * (rhs / gcd);
+        let denom = self.denom / gcd;
         Self { num, denom }.normalize()
     }
 }
 
+impl<T> Rem for Fraction<T>
+where
+    T: Copy,
+    T: num::Integer,
+{
+    type Output = Fraction<T>;
+
+    /// The remainder after truncating division, matching the
+    /// behavior of the `%` operator for primitive integers.
+    fn rem(self, rhs: Self) -> Self::Output {
+        let quotient = self / rhs;
+        let truncated = quotient.num / quotient.denom;
+        self - rhs * truncated
+    }
+}
+
 impl<T> Sum for Fraction<T>
 where
     T: num::Integer,
     T: Copy,
 {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(
-            Fraction {
-                num: T::zero(),
-                denom: T::one(),
-            },
-            |a, b| a + b,
-        )
+        iter.fold(num::Zero::zero(), |a, b| a + b)
+    }
+}
+
+impl<T> Product for Fraction<T>
+where
+    T: num::Integer,
+    T: Copy,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(num::One::one(), |a, b| a * b)
     }
 }
 
@@ -306,3 +530,325 @@ where
         }
     }
 }
+
+impl<T> num::Num for Fraction<T>
+where
+    T: num::Integer,
+    T: Copy,
+{
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    /// Parse either an integer, or a fraction of the form "num/denom".
+    fn from_str_radix(
+        s: &str,
+        radix: u32,
+    ) -> Result<Self, Self::FromStrRadixErr> {
+        let fraction = if let Some((num, denom)) = s.split_once('/') {
+            Self::new(
+                T::from_str_radix(num, radix)?,
+                T::from_str_radix(denom, radix)?,
+            )
+        } else {
+            T::from_str_radix(s, radix)?.into()
+        };
+        Ok(fraction.normalize())
+    }
+}
+
+impl<T> num::Signed for Fraction<T>
+where
+    T: num::Integer,
+    T: num::Signed,
+    T: Copy,
+{
+    fn abs(&self) -> Self {
+        Self {
+            num: self.num.abs(),
+            denom: self.denom.abs(),
+        }
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            num::Zero::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        (self.num.signum() * self.denom.signum()).into()
+    }
+
+    fn is_positive(&self) -> bool {
+        self.num.is_positive() == self.denom.is_positive()
+            && !self.num.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.num.is_positive() != self.denom.is_positive()
+            && !self.num.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::geometry::Vector;
+    use num::{Num, One, Signed, Zero};
+
+    #[test]
+    fn test_add() {
+        assert_eq!(
+            Fraction::new(1, 2) + Fraction::new(1, 3),
+            Fraction::new(5, 6)
+        );
+        let sum = Fraction::new(1, 6) + Fraction::new(1, 3);
+        assert_eq!((sum.num, sum.denom), (1, 2));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(
+            Fraction::new(1, 2) - Fraction::new(1, 3),
+            Fraction::new(1, 6)
+        );
+        let diff = Fraction::new(1, 3) - Fraction::new(1, 2);
+        assert_eq!((diff.num, diff.denom), (-1, 6));
+    }
+
+    #[test]
+    fn test_mul() {
+        let prod = Fraction::new(2, 3) * Fraction::new(9, 4);
+        assert_eq!((prod.num, prod.denom), (3, 2));
+        let prod = Fraction::new(5, 6) * 4;
+        assert_eq!((prod.num, prod.denom), (10, 3));
+        let prod = Fraction::new(0, 6) * Fraction::new(-5, 7);
+        assert_eq!((prod.num, prod.denom), (0, 1));
+    }
+
+    #[test]
+    fn test_mul_cancels_before_overflow() {
+        let a = Fraction::<i32>::new(1 << 20, 3);
+        let b = Fraction::<i32>::new(3, 1 << 20);
+        let prod = a * b;
+        assert_eq!((prod.num, prod.denom), (1, 1));
+    }
+
+    #[test]
+    fn test_div() {
+        let quot = Fraction::new(2, 3) / Fraction::new(-4, 9);
+        assert_eq!((quot.num, quot.denom), (-3, 2));
+        let quot = Fraction::new(6, 5) / 4;
+        assert_eq!((quot.num, quot.denom), (3, 10));
+    }
+
+    #[test]
+    fn test_neg() {
+        let neg = -Fraction::new(2, 3);
+        assert_eq!((neg.num, neg.denom), (-2, 3));
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Fraction::new(2, 4), Fraction::new(1, 2));
+        assert_eq!(Fraction::new(-1, -2), Fraction::new(1, 2));
+        assert_eq!(Fraction::new(6, 3), 2);
+        assert_ne!(Fraction::new(1, 3), Fraction::new(1, 2));
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(Fraction::new(1, 3) < Fraction::new(1, 2));
+        assert!(Fraction::new(-1, 2) < Fraction::new(-1, 3));
+        assert!(Fraction::new(1, -2) < Fraction::new(1, 3));
+        assert!(Fraction::new(-3, -4) > Fraction::new(2, 3));
+        assert_eq!(
+            Fraction::new(2, 4).cmp(&Fraction::new(1, 2)),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_ord_integer() {
+        assert!(Fraction::new(3, 2) > 1);
+        assert!(Fraction::new(3, 2) < 2);
+        assert!(Fraction::new(-3, 2) < -1);
+        assert!(Fraction::new(3, -2) < -1);
+        assert!(Fraction::new(-3, -2) > 1);
+        assert!(Fraction::new(4, 2) <= 2);
+        assert!(Fraction::new(4, 2) >= 2);
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(Fraction::new(3, 2).clamp(0, 1), Fraction::from(1));
+        assert_eq!(Fraction::new(-1, 2).clamp(0, 1), Fraction::from(0));
+        assert_eq!(Fraction::new(1, 3).clamp(0, 1), Fraction::new(1, 3));
+        assert_eq!(
+            Fraction::new(1, 3).clamp(Fraction::new(1, 2), 1),
+            Fraction::new(1, 2)
+        );
+
+        let unit = Fraction::from(0)..=Fraction::from(1);
+        assert!(unit.contains(&Fraction::new(1, 3)));
+        assert!(unit.contains(&Fraction::new(2, 2)));
+        assert!(!unit.contains(&Fraction::new(4, 3)));
+
+        assert_eq!(
+            Fraction::new(1, 3).max(Fraction::new(1, 2)),
+            Fraction::new(1, 2)
+        );
+    }
+
+    #[test]
+    fn test_from_f64_approx() {
+        let third = Fraction::from_f64_approx(0.333_333_333_3, 1000).unwrap();
+        assert_eq!((third.num, third.denom), (1, 3));
+
+        let half = Fraction::from_f64_approx(0.5, 1000).unwrap();
+        assert_eq!((half.num, half.denom), (1, 2));
+
+        let pi = Fraction::from_f64_approx(std::f64::consts::PI, 1000).unwrap();
+        assert_eq!((pi.num, pi.denom), (355, 113));
+
+        let pi = Fraction::from_f64_approx(std::f64::consts::PI, 100).unwrap();
+        assert_eq!((pi.num, pi.denom), (22, 7));
+
+        let neg = Fraction::from_f64_approx(-2.75, 1000).unwrap();
+        assert_eq!((neg.num, neg.denom), (-11, 4));
+
+        let whole = Fraction::from_f64_approx(7.0, 1000).unwrap();
+        assert_eq!((whole.num, whole.denom), (7, 1));
+    }
+
+    #[test]
+    fn test_from_f64_approx_invalid() {
+        assert_eq!(Fraction::<i64>::from_f64_approx(f64::NAN, 1000), None);
+        assert_eq!(Fraction::<i64>::from_f64_approx(f64::INFINITY, 1000), None);
+        assert_eq!(
+            Fraction::<i64>::from_f64_approx(f64::NEG_INFINITY, 1000),
+            None
+        );
+        assert_eq!(Fraction::<i8>::from_f64_approx(1000.5, 100), None);
+        assert_eq!(Fraction::<i64>::from_f64_approx(0.5, 0), None);
+        assert_eq!(Fraction::<i64>::from_f64_approx(0.5, -3), None);
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(Fraction::new(3, 4).to_f64(), 0.75);
+        assert_eq!(Fraction::<i128>::new(-1, 8).to_f64(), -0.125);
+    }
+
+    #[test]
+    fn test_display_decimal() {
+        let frac = Fraction::new(2, 3);
+        assert_eq!(format!("{}", frac.display_decimal(4)), "0.6667");
+        assert_eq!(format!("{}", frac.display_decimal(0)), "1");
+        assert_eq!(
+            format!("{}", Fraction::new(-7, 2).display_decimal(2)),
+            "-3.50"
+        );
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let a = Fraction::<i8>::new(1, 2);
+        let b = Fraction::<i8>::new(1, 3);
+        assert_eq!(a.checked_add(b), Some(Fraction::new(5, 6)));
+        assert_eq!(a.checked_sub(b), Some(Fraction::new(1, 6)));
+        assert_eq!(a.checked_mul(b), Some(Fraction::new(1, 6)));
+        assert_eq!(a.checked_div(b), Some(Fraction::new(3, 2)));
+        assert_eq!(a.checked_div(Fraction::new(0, 1)), None);
+    }
+
+    #[test]
+    fn test_checked_ops_overflow() {
+        let a = Fraction::<i8>::new(1, 11);
+        let b = Fraction::<i8>::new(1, 13);
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.checked_sub(b), None);
+        assert_eq!(a.checked_mul(b), None);
+
+        // Common factors are cancelled before multiplying.
+        let c = Fraction::<i8>::new(100, 3);
+        let d = Fraction::<i8>::new(3, 100);
+        assert_eq!(c.checked_mul(d), Some(Fraction::new(1, 1)));
+        assert_eq!(c.checked_div(c), Some(Fraction::new(1, 1)));
+    }
+
+    #[test]
+    fn test_rem() {
+        let rem = Fraction::new(7, 2) % Fraction::new(4, 3);
+        assert_eq!((rem.num, rem.denom), (5, 6));
+        let rem = Fraction::new(-7, 2) % Fraction::new(4, 3);
+        assert_eq!((rem.num, rem.denom), (-5, 6));
+    }
+
+    #[test]
+    fn test_zero_one() {
+        assert!(Fraction::<i64>::zero().is_zero());
+        assert!(Fraction::new(0, 5).is_zero());
+        assert!(Fraction::<i64>::one().is_one());
+
+        let vec = Vector::<3, Fraction<i64>>::zero();
+        assert!(vec.is_zero());
+        let one_hot = Vector::<3, Fraction<i64>>::one_hot(1);
+        assert!(!one_hot.is_zero());
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let fractions = [
+            Fraction::new(1, 2),
+            Fraction::new(1, 3),
+            Fraction::new(1, 6),
+        ];
+        assert_eq!(fractions.into_iter().sum::<Fraction<i64>>(), 1);
+        assert_eq!(
+            fractions.into_iter().product::<Fraction<i64>>(),
+            Fraction::new(1, 36)
+        );
+        assert!(std::iter::empty::<Fraction<i64>>()
+            .sum::<Fraction<_>>()
+            .is_zero());
+        assert!(std::iter::empty::<Fraction<i64>>()
+            .product::<Fraction<_>>()
+            .is_one());
+
+        let vectors = [
+            Vector::new([Fraction::new(1, 2), Fraction::from(1)]),
+            Vector::new([Fraction::new(1, 3), Fraction::new(-1, 4)]),
+            Vector::new([Fraction::new(1, 6), Fraction::new(1, 4)]),
+        ];
+        let total: Vector<2, Fraction<i64>> = vectors.into_iter().sum();
+        assert_eq!(total, Vector::new([Fraction::from(1), Fraction::from(1)]));
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        let frac = Fraction::<i64>::from_str_radix("6/-4", 10).unwrap();
+        assert_eq!((frac.num, frac.denom), (-3, 2));
+        let frac = Fraction::<i64>::from_str_radix("ff", 16).unwrap();
+        assert_eq!((frac.num, frac.denom), (255, 1));
+        assert!(Fraction::<i64>::from_str_radix("1/", 10).is_err());
+    }
+
+    #[test]
+    fn test_signed() {
+        let frac = Fraction::new(3, -4);
+        assert!(frac.is_negative());
+        assert!(!frac.is_positive());
+        assert_eq!(frac.abs(), Fraction::new(3, 4));
+        assert_eq!(frac.signum(), Fraction::from(-1));
+        assert!(Fraction::new(-3, -4).is_positive());
+        assert!(!Fraction::new(0, -4).is_negative());
+        assert_eq!(
+            Fraction::new(1, 2).abs_sub(&Fraction::new(1, 3)),
+            Fraction::new(1, 6)
+        );
+        assert!(Fraction::new(1, 3).abs_sub(&Fraction::new(1, 2)).is_zero());
+    }
+}
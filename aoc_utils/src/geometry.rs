@@ -1,5 +1,7 @@
 use aoc_framework::Error;
 
+use crate::{render_table, Fraction};
+
 use std::cmp;
 use std::fmt::{Display, Formatter};
 use std::ops;
@@ -13,6 +15,14 @@ pub struct Vector<const N: usize, T = i64>([T; N]);
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Matrix<const N: usize, const M: usize, T = i64>([Vector<M, T>; N]);
 
+/// The smallest axis-aligned box containing a set of points.  Both
+/// corners are inclusive.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct BoundingBox<const N: usize, T = i64> {
+    pub min: Vector<N, T>,
+    pub max: Vector<N, T>,
+}
+
 pub struct DisplayHelper<'a, T> {
     item: &'a T,
     line_prefix: Option<&'a str>,
@@ -72,6 +82,8 @@ macro_rules! elementwise_scalar_op {
         // over the generic type T isn't covered by any local type.
         // Therefore, implementing for several common types, and
         // crossing fingers that it doesn't become an issue later.
+        // For any other type, `Vector::scale` and `Matrix::scale`
+        // can be used instead.
         elementwise_scalar_op_lhs!($trait, $method, $op, i8);
         elementwise_scalar_op_lhs!($trait, $method, $op, i16);
         elementwise_scalar_op_lhs!($trait, $method, $op, i32);
@@ -189,29 +201,22 @@ where
     T: Display,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let col_widths: [usize; M] = std::array::from_fn(|i| {
-            (0..N)
-                .map(|j| format!("{}", self.item[(j, i)]).len())
-                .max()
-                .unwrap_or(0)
-        });
-        let total_width = col_widths.iter().map(|w| w + 2).sum();
+        let rows: Vec<Vec<String>> = self
+            .item
+            .iter_rows()
+            .map(|row| row.iter().map(|item| item.to_string()).collect())
+            .collect();
         let prefix = &self.line_prefix.unwrap_or("");
 
-        if self.prefix_first_line {
-            write!(f, "{prefix}")?;
-        }
-
-        writeln!(f, "┌{:width$}┐", "", width = total_width)?;
-        self.item.iter_rows().try_for_each(|row| {
-            write!(f, "{prefix}|")?;
-            row.iter()
-                .zip(col_widths.iter())
-                .try_for_each(|(item, width)| write!(f, " {item:width$} "))?;
-            writeln!(f, "|")
-        })?;
-        writeln!(f, "{prefix}└{:width$}┘", "", width = total_width)?;
-        Ok(())
+        render_table(&rows)
+            .lines()
+            .enumerate()
+            .try_for_each(|(i, line)| {
+                if i > 0 || self.prefix_first_line {
+                    write!(f, "{prefix}")?;
+                }
+                writeln!(f, "{line}")
+            })
     }
 }
 
@@ -338,13 +343,15 @@ impl<const N: usize, T> Vector<N, T> {
         self.0.swap(a, b)
     }
 
-    pub fn mag2(self) -> T
+    /// The squared magnitude of the vector.
+    pub fn mag2(&self) -> T
     where
         T: Copy,
-        T: ops::Mul<Output = T>,
         T: std::iter::Sum,
+        T: num::Zero,
+        T: std::ops::Mul<T, Output = T>,
     {
-        self.into_iter().map(|a| a * a).sum()
+        self.dot_product(*self)
     }
 
     pub fn dist2(&self, other: &Self) -> T
@@ -370,6 +377,58 @@ impl<const N: usize, T> Vector<N, T> {
             .sum()
     }
 
+    /// The elementwise minimum of two vectors.
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: Ord + Copy,
+    {
+        std::array::from_fn(|i| cmp::min(self[i], other[i])).into()
+    }
+
+    /// The elementwise maximum of two vectors.
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: Ord + Copy,
+    {
+        std::array::from_fn(|i| cmp::max(self[i], other[i])).into()
+    }
+
+    /// Multiply each element by a scalar, `scalar * element`.  Unlike
+    /// the `scalar * vector` operator, this is available for any
+    /// element type, not just primitives.
+    pub fn scale(self, scalar: T) -> Self
+    where
+        T: ops::Mul<Output = T> + Copy,
+    {
+        self.map(|val| scalar * val)
+    }
+
+    /// Elementwise addition, returning `None` if any component
+    /// overflows.
+    pub fn checked_add(self, other: Self) -> Option<Self>
+    where
+        T: num::CheckedAdd + Copy,
+    {
+        let mut result = self;
+        for i in 0..N {
+            result[i] = self[i].checked_add(&other[i])?;
+        }
+        Some(result)
+    }
+
+    /// Multiply each element by a scalar, returning `None` if any
+    /// component overflows.
+    pub fn checked_mul_scalar(self, scalar: T) -> Option<Self>
+    where
+        T: num::CheckedMul + Copy,
+    {
+        let mut result = self;
+        for i in 0..N {
+            result[i] = self[i].checked_mul(&scalar)?;
+        }
+        Some(result)
+    }
+
     pub fn map<U, F>(self, func: F) -> Vector<N, U>
     where
         F: FnMut(T) -> U,
@@ -416,6 +475,7 @@ impl<const N: usize, T> Vector<N, T> {
         //     .take((len + T::one()).into())
     }
 
+    /// The sum of the elementwise products of two vectors.
     pub fn dot_product(self, other: Self) -> T
     where
         T: std::iter::Sum,
@@ -429,6 +489,156 @@ impl<const N: usize, T> Vector<N, T> {
     }
 }
 
+/// Whether `point` lies on the forward half-line starting at `origin`
+/// and extending along `velocity`, i.e. whether `point = origin +
+/// t*velocity` for some `t >= 0`.  The origin itself is always ahead.
+/// If the velocity is zero, only the origin is ahead.
+pub fn is_point_ahead<const N: usize, T>(
+    origin: Vector<N, T>,
+    velocity: Vector<N, T>,
+    point: Vector<N, T>,
+) -> bool
+where
+    T: Copy + PartialOrd + num::Zero,
+    T: ops::Sub<Output = T> + ops::Mul<Output = T>,
+{
+    let delta: Vector<N, T> =
+        std::array::from_fn(|i| point[i] - origin[i]).into();
+    if num::Zero::is_zero(&velocity) {
+        return num::Zero::is_zero(&delta);
+    }
+
+    let is_collinear = (0..N).all(|i| {
+        (0..i).all(|j| delta[i] * velocity[j] == delta[j] * velocity[i])
+    });
+    let is_forward = (0..N).all(|i| delta[i] * velocity[i] >= T::zero());
+
+    is_collinear && is_forward
+}
+
+/// Twice the signed area of the triangle `abc`.  Positive if the
+/// vertices are in counter-clockwise order (with y increasing
+/// upward), negative if clockwise, and zero if collinear.
+pub fn triangle_area2(
+    a: Vector<2, i64>,
+    b: Vector<2, i64>,
+    c: Vector<2, i64>,
+) -> i64 {
+    (b.x() - a.x()) * (c.y() - a.y()) - (b.y() - a.y()) * (c.x() - a.x())
+}
+
+/// Whether `point` lies within the polygon whose vertices are given in
+/// order, with an implicit edge from the last vertex back to the
+/// first.  Points on the boundary are considered to be within the
+/// polygon.  Uses ray casting along the +x direction, with exact
+/// integer arithmetic.
+pub fn point_in_polygon(
+    point: Vector<2, i64>,
+    polygon: &[Vector<2, i64>],
+) -> bool {
+    let edges = || {
+        polygon
+            .iter()
+            .copied()
+            .zip(polygon.iter().copied().cycle().skip(1))
+    };
+
+    let on_boundary = edges().any(|(a, b)| {
+        triangle_area2(a, b, point) == 0
+            && a.x().min(b.x()) <= point.x()
+            && point.x() <= a.x().max(b.x())
+            && a.y().min(b.y()) <= point.y()
+            && point.y() <= a.y().max(b.y())
+    });
+    if on_boundary {
+        return true;
+    }
+
+    // Count the edges that cross the ray.  Treating each edge as
+    // half-open in y avoids double-counting a vertex shared by two
+    // edges.
+    let num_crossings = edges()
+        .filter(|(a, b)| (a.y() > point.y()) != (b.y() > point.y()))
+        .filter(|(a, b)| {
+            // The edge crosses the ray's line to the right of the
+            // point if point.x < a.x + (point.y-a.y)*(b.x-a.x)/(b.y-a.y)
+            let lhs = (point.x() - a.x()) * (b.y() - a.y());
+            let rhs = (point.y() - a.y()) * (b.x() - a.x());
+            if b.y() > a.y() {
+                lhs < rhs
+            } else {
+                lhs > rhs
+            }
+        })
+        .count();
+    num_crossings % 2 == 1
+}
+
+/// Iterate over every lattice point in the inclusive box from `min`
+/// to `max`.  The first coordinate varies fastest.  If `max` is less
+/// than `min` along any axis, the box is empty.
+pub fn iter_box<const N: usize>(
+    min: Vector<N, i64>,
+    max: Vector<N, i64>,
+) -> impl Iterator<Item = Vector<N, i64>> {
+    let is_empty = (0..N).any(|i| min[i] > max[i]);
+    std::iter::successors((!is_empty).then_some(min), move |&prev| {
+        let mut next = prev;
+        for i in 0..N {
+            if next[i] < max[i] {
+                next[i] += 1;
+                return Some(next);
+            }
+            next[i] = min[i];
+        }
+        None
+    })
+}
+
+impl<const N: usize, T> BoundingBox<N, T>
+where
+    T: Ord + Copy,
+{
+    /// A box containing only a single point.
+    pub fn new(point: Vector<N, T>) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The bounding box of all points in the iterator, or None if the
+    /// iterator is empty.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Vector<N, T>>,
+    ) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = Self::new(iter.next()?);
+        Some(iter.fold(first, |bbox, point| bbox.include(point)))
+    }
+
+    /// Expand the box, if necessary, to contain the point.
+    pub fn include(self, point: Vector<N, T>) -> Self {
+        Self {
+            min: self.min.component_min(point),
+            max: self.max.component_max(point),
+        }
+    }
+
+    pub fn contains(&self, point: &Vector<N, T>) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    /// The number of integer points along each dimension of the box.
+    pub fn shape(&self) -> Vector<N, T>
+    where
+        T: num::One,
+        T: ops::Add<Output = T> + ops::Sub<Output = T>,
+    {
+        (self.max - self.min).map(|extent| extent + T::one())
+    }
+}
+
 impl<const N: usize, T> Default for Vector<N, T>
 where
     T: Default,
@@ -454,6 +664,29 @@ impl<T> Vector<2, T> {
     }
 }
 
+impl<T> Vector<2, T> {
+    /// Rotate by 90 degrees clockwise, with x increasing to the right
+    /// and y increasing upward.  With y increasing downward, as in
+    /// `GridMap`, this appears as a counter-clockwise rotation.
+    pub fn rotate_cw_90(self) -> Self
+    where
+        T: Copy + ops::Neg<Output = T>,
+    {
+        Vector::new([self.y(), -self.x()])
+    }
+
+    /// Rotate by 90 degrees counter-clockwise, with x increasing to
+    /// the right and y increasing upward.  Equivalent to
+    /// `Matrix::<2, 2>::rotate() * self`.  With y increasing downward,
+    /// as in `GridMap`, this appears as a clockwise rotation.
+    pub fn rotate_ccw_90(self) -> Self
+    where
+        T: Copy + ops::Neg<Output = T>,
+    {
+        Vector::new([-self.y(), self.x()])
+    }
+}
+
 impl<T> Vector<3, T> {
     pub fn x(&self) -> T
     where
@@ -477,6 +710,58 @@ impl<T> Vector<3, T> {
     }
 }
 
+impl<T> Vector<3, T> {
+    /// Convert to homogeneous coordinates, with `w = 1`.
+    pub fn to_homogeneous(self) -> Vector<4, T>
+    where
+        T: Copy + num::One,
+    {
+        Vector::new([self.x(), self.y(), self.z(), T::one()])
+    }
+
+    /// Convert from homogeneous coordinates, dividing by `w`.  For
+    /// affine transforms, including those produced by
+    /// `Matrix::<4, 4>::translation`, `w` remains 1 and the division
+    /// is exact.
+    pub fn from_homogeneous(vec: Vector<4, T>) -> Self
+    where
+        T: Copy + ops::Div<Output = T>,
+    {
+        let w = vec.w();
+        Vector::new([vec.x() / w, vec.y() / w, vec.z() / w])
+    }
+}
+
+impl<T> Vector<4, T> {
+    pub fn x(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[2]
+    }
+
+    pub fn w(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[3]
+    }
+}
+
 impl<const N: usize, T> From<[T; N]> for Vector<N, T> {
     fn from(values: [T; N]) -> Self {
         Self::new(values)
@@ -539,6 +824,16 @@ impl<const N: usize, const M: usize, T> Matrix<N, M, T> {
         Self(rows.map(|row| row.into()))
     }
 
+    /// Construct a matrix from a list of columns, ordered from left
+    /// to right.  Equivalent to transposing the result of
+    /// `Matrix::new`.
+    pub fn from_columns<Col>(cols: [Col; M]) -> Self
+    where
+        Col: Into<Vector<N, T>>,
+    {
+        Matrix::<M, N, T>::new(cols).transpose()
+    }
+
     pub fn transpose(self) -> Matrix<M, N, T> {
         let mut take_from = self.0.map(|row| row.map(|item| Some(item)));
 
@@ -553,6 +848,24 @@ impl<const N: usize, const M: usize, T> Matrix<N, M, T> {
         }))
     }
 
+    /// Multiply each element by a scalar, `scalar * element`.  Unlike
+    /// the `scalar * matrix` operator, this is available for any
+    /// element type, not just primitives.
+    pub fn scale(self, scalar: T) -> Self
+    where
+        T: ops::Mul<Output = T> + Copy,
+    {
+        Self(self.0.map(|row| row.scale(scalar)))
+    }
+
+    /// Apply a function to each element, in row-major order.
+    pub fn map<U, F>(self, mut func: F) -> Matrix<N, M, U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Matrix(self.0.map(|row| row.map(&mut func)))
+    }
+
     pub fn iter_rows(&self) -> impl Iterator<Item = &Vector<M, T>> + '_ {
         self.0.iter()
     }
@@ -614,7 +927,37 @@ impl<const N: usize, T> Matrix<N, N, T> {
         T: ops::Mul<Output = T>,
         T: std::iter::Sum,
     {
-        (0..power).fold(Self::identity(), |cum_prod, _i| *self * cum_prod)
+        // Exponentiation by squaring, iterating over the bits of the
+        // power from least to most significant.
+        let mut result = Self::identity();
+        let mut square = *self;
+        let mut power = power;
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result * square;
+            }
+            power >>= 1;
+            if power > 0 {
+                square = square * square;
+            }
+        }
+        result
+    }
+
+    /// Whether the matrix is equal to its own transpose.
+    pub fn is_symmetric(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        (0..N).all(|i| (0..i).all(|j| self[(i, j)] == self[(j, i)]))
+    }
+
+    /// Whether every element off of the main diagonal is zero.
+    pub fn is_diagonal(&self) -> bool
+    where
+        T: num::Zero,
+    {
+        (0..N).all(|i| (0..N).all(|j| i == j || self[(i, j)].is_zero()))
     }
 }
 
@@ -629,6 +972,29 @@ impl<T> Matrix<2, 2, T> {
     }
 }
 
+impl<T> Matrix<4, 4, T> {
+    /// A homogeneous transform that translates by `offset`.
+    pub fn translation(offset: Vector<3, T>) -> Self
+    where
+        T: Copy + num::Zero + num::One,
+    {
+        let mut mat = Self::identity();
+        (0..3).for_each(|i| mat[(i, 3)] = offset[i]);
+        mat
+    }
+
+    /// A homogeneous transform that applies the linear transform
+    /// `linear`, such as a rotation, about the origin.
+    pub fn from_linear(linear: Matrix<3, 3, T>) -> Self
+    where
+        T: Copy + num::Zero + num::One,
+    {
+        let mut mat = Self::identity();
+        (0..3).for_each(|i| (0..3).for_each(|j| mat[(i, j)] = linear[(i, j)]));
+        mat
+    }
+}
+
 impl Matrix<3, 3> {
     // 90 degree rotation about the x axis.
     pub fn rotate_x() -> Self {
@@ -667,6 +1033,224 @@ impl Matrix<3, 3> {
     }
 }
 
+/// A system of linear equations, `matrix * x = augment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearSystem<const ROWS: usize, const COLS: usize, T = i64> {
+    pub matrix: Matrix<ROWS, COLS, T>,
+    pub augment: Vector<ROWS, T>,
+}
+
+/// The set of all points `offset + sum(c_i * basis_states[i])`, for
+/// arbitrary coefficients `c_i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffineLinearSpace<const N: usize, T> {
+    offset: Vector<N, T>,
+    basis_states: Vec<Vector<N, T>>,
+}
+
+impl<const N: usize, T> AffineLinearSpace<N, T> {
+    /// A point that lies within the space.
+    pub fn offset(&self) -> &Vector<N, T> {
+        &self.offset
+    }
+
+    /// Directions along which the space extends.  Empty if the space
+    /// consists of a single point.
+    pub fn basis_states(&self) -> &[Vector<N, T>] {
+        &self.basis_states
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T> Display
+    for LinearSystem<ROWS, COLS, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<Vec<String>> = (0..ROWS)
+            .map(|j| {
+                self.matrix[j]
+                    .iter()
+                    .map(|item| item.to_string())
+                    .chain(std::iter::once(format!("| {}", self.augment[j])))
+                    .collect()
+            })
+            .collect();
+        write!(f, "{}", render_table(&rows))
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T> LinearSystem<ROWS, COLS, T> {
+    pub fn new(
+        matrix: Matrix<ROWS, COLS, T>,
+        augment: Vector<ROWS, T>,
+    ) -> Self {
+        Self { matrix, augment }
+    }
+
+    fn normalize_equation(&mut self, i: usize)
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::Signed,
+    {
+        // Not technically required here, but canceling out
+        // unnecessary factors in each row avoids some integer
+        // overflow cases.
+        if let Some(gcd) = self.matrix[i]
+            .iter()
+            .chain(std::iter::once(&self.augment[i]))
+            .cloned()
+            .reduce(num::integer::gcd)
+        {
+            let leading_sign = self.matrix[i]
+                .iter()
+                .find(|val| !val.is_zero())
+                .map(|val| val.signum())
+                .unwrap_or(T::one());
+            let gcd = gcd * leading_sign;
+
+            if !gcd.is_zero() {
+                self.matrix[i] = self.matrix[i] / gcd;
+                self.augment[i] = self.augment[i] / gcd;
+            }
+        }
+    }
+
+    fn row_echelon_form(mut self) -> Self
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::Signed,
+    {
+        for row in 0..ROWS {
+            self.normalize_equation(row);
+        }
+
+        let mut row = 0;
+
+        for column in 0..COLS {
+            if let Some(nonzero_row_i) =
+                (row..ROWS).find(|&j| !self.matrix[j][column].is_zero())
+            {
+                if row != nonzero_row_i {
+                    self.matrix.swap_rows(row, nonzero_row_i);
+                    self.augment.swap(row, nonzero_row_i);
+                }
+
+                // This loop could be reduced to only cover (i+1..M)
+                // and still produce a correct row echelon form, since
+                // that would provide the leading zeros for later
+                // rows.  This way, the only step remaining for rref
+                // is to scale each row to have a leading value of
+                // one.  This isn't done by default, as it would also
+                // require changing the type from T to Fraction<T>.
+                for j in 0..ROWS {
+                    if row != j && !self.matrix[j][column].is_zero() {
+                        let a = self.matrix[row][column];
+                        let b = self.matrix[j][column];
+                        let gcd = num::integer::gcd(a, b);
+
+                        self.matrix[j] = self.matrix[j] * (a / gcd)
+                            - self.matrix[row] * (b / gcd);
+
+                        self.augment[j] = self.augment[j] * (a / gcd)
+                            - self.augment[row] * (b / gcd);
+                        self.normalize_equation(j);
+                    }
+                }
+                row += 1;
+            }
+        }
+
+        self
+    }
+
+    /// Find all solutions to the system of equations.  Returns None
+    /// if the system is inconsistent and has no solutions.
+    pub fn solve(&self) -> Option<AffineLinearSpace<COLS, Fraction<T>>>
+    where
+        T: Copy,
+        T: num::Integer,
+        T: num::Signed,
+    {
+        let LinearSystem {
+            matrix: echelon_form,
+            augment: solution,
+        } = self.clone().row_echelon_form();
+
+        let row_of_zeros: [bool; ROWS] = std::array::from_fn(|i| {
+            (0..COLS).all(|j| echelon_form[(i, j)].is_zero())
+        });
+
+        let rank = row_of_zeros.iter().map(|b| !b as usize).sum::<usize>();
+        assert!(rank <= ROWS);
+        assert!(rank <= COLS);
+
+        // If the system of equations is inconsistent, the LHS will
+        // contain a fully-canceled row, but the RHS will not cancel
+        // out entirely.
+        let is_consistent = solution
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| row_of_zeros[*i])
+            .all(|(_, t)| T::is_zero(t));
+
+        if !is_consistent {
+            return None;
+        }
+
+        let leading_terms: [Option<usize>; ROWS] = std::array::from_fn(|row| {
+            echelon_form[row]
+                .iter()
+                .enumerate()
+                .find(|(_, element)| !element.is_zero())
+                .map(|(col, _)| col)
+        });
+
+        // The leading non-zero term in each row is used to determine
+        // a point that lies within the solution space.
+        let offset: Vector<COLS, Fraction<T>> = (0..ROWS)
+            .filter_map(|row| {
+                leading_terms[row].map(|col| {
+                    let value =
+                        Fraction::new(solution[row], echelon_form[(row, col)]);
+                    Vector::<COLS, _>::one_hot(col) * value.normalize()
+                })
+            })
+            .sum();
+
+        // Columns that do not contain a leading non-zero term are
+        // free parameters, and each determine one basis vector of
+        // the solution space.  If the rank is equal to the number of
+        // columns, the solution is unique and there are no basis
+        // vectors.
+        let basis_states: Vec<Vector<COLS, Fraction<T>>> = (0..COLS)
+            .filter(|col| !leading_terms.iter().contains(&Some(*col)))
+            .map(|col| {
+                (0..ROWS)
+                    .filter_map(|row| {
+                        leading_terms[row].map(|leading_col| (row, leading_col))
+                    })
+                    .map(|(row, leading_col)| {
+                        let value = Fraction::new(
+                            -echelon_form[(row, col)],
+                            echelon_form[(row, leading_col)],
+                        );
+                        Vector::<COLS, _>::one_hot(leading_col)
+                            * value.normalize()
+                    })
+                    .fold(Vector::one_hot(col), |a, b| a + b)
+            })
+            .collect();
+
+        Some(AffineLinearSpace {
+            offset,
+            basis_states,
+        })
+    }
+}
+
 impl<const N: usize, T> FromStr for Vector<N, T>
 where
     T: Default,
@@ -701,6 +1285,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num::Zero;
 
     #[test]
     fn test_vector_parse() {
@@ -717,6 +1302,56 @@ mod tests {
         assert_eq!(a + b, c);
     }
 
+    #[test]
+    fn test_vector_checked_add() {
+        let a = Vector::new([1, 2, 3]);
+        let b = Vector::new([10, 20, 30]);
+        assert_eq!(a.checked_add(b), Some(Vector::new([11, 22, 33])));
+
+        let near_max = Vector::new([i64::MAX - 1, 0]);
+        assert_eq!(
+            near_max.checked_add(Vector::new([1, 5])),
+            Some(Vector::new([i64::MAX, 5]))
+        );
+        assert_eq!(near_max.checked_add(Vector::new([2, 5])), None);
+        assert_eq!(
+            Vector::new([0, i64::MIN]).checked_add(Vector::new([0, -1])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_vector_checked_mul_scalar() {
+        let a = Vector::new([1, -2, 3]);
+        assert_eq!(a.checked_mul_scalar(4), Some(Vector::new([4, -8, 12])));
+
+        let large = Vector::new([400_000_000_000_000i64, 1]);
+        assert_eq!(large.checked_mul_scalar(100_000), None);
+    }
+
+    #[test]
+    fn test_iter_box() {
+        let min = Vector::new([0, 0, 0]);
+        let max = Vector::new([1, 2, 3]);
+        assert_eq!(iter_box(min, max).count(), 24);
+        assert_eq!(iter_box(min, max).unique().count(), 24);
+        assert!(iter_box(min, max).all(|point| {
+            (0..3).all(|i| (min[i]..=max[i]).contains(&point[i]))
+        }));
+
+        let points: Vec<(i64, i64)> =
+            iter_box(Vector::new([-1, 5]), Vector::new([0, 6]))
+                .map(|point| point.into())
+                .collect();
+        assert_eq!(points, vec![(-1, 5), (0, 5), (-1, 6), (0, 6)]);
+
+        assert_eq!(iter_box(Vector::new([2]), Vector::new([2])).count(), 1);
+        assert_eq!(
+            iter_box(Vector::new([0, 3]), Vector::new([5, 2])).count(),
+            0
+        );
+    }
+
     #[test]
     fn test_vector_sub() {
         let a = Vector::new([10, 20, 30, 40, 50, 60]);
@@ -746,6 +1381,220 @@ mod tests {
         assert_eq!(a / 2, b);
     }
 
+    #[test]
+    fn test_vector_scale_generic() {
+        let a: Vector<2, Fraction<i128>> =
+            Vector::new([Fraction::new(1, 2), Fraction::from(3)]);
+        let b = Vector::new([Fraction::new(1, 4), Fraction::new(3, 2)]);
+        assert_eq!(a.scale(Fraction::new(1, 2)), b);
+        assert_eq!(Vector::new([1, 2, 3]).scale(2), Vector::new([2, 4, 6]));
+    }
+
+    #[test]
+    fn test_matrix_scale_generic() {
+        let a: Matrix<2, 2, Fraction<i128>> = Matrix::new([
+            [Fraction::from(1), Fraction::new(1, 3)],
+            [Fraction::new(2, 3), Fraction::from(0)],
+        ]);
+        let b = Matrix::new([
+            [Fraction::from(3), Fraction::from(1)],
+            [Fraction::from(2), Fraction::from(0)],
+        ]);
+        assert_eq!(a.scale(Fraction::from(3)), b);
+    }
+
+    #[test]
+    fn test_is_point_ahead() {
+        let origin: Vector<2, i64> = Vector::new([1, 2]);
+        let velocity = Vector::new([2, -1]);
+
+        // Ahead of the origin
+        assert!(is_point_ahead(origin, velocity, Vector::new([5, 0])));
+        // At the origin
+        assert!(is_point_ahead(origin, velocity, origin));
+        // Behind the origin
+        assert!(!is_point_ahead(origin, velocity, Vector::new([-1, 3])));
+        // Not on the line
+        assert!(!is_point_ahead(origin, velocity, Vector::new([5, 1])));
+
+        let stationary = Vector::zero();
+        assert!(is_point_ahead(origin, stationary, origin));
+        assert!(!is_point_ahead(origin, stationary, Vector::new([5, 0])));
+    }
+
+    #[test]
+    fn test_is_point_ahead_fractional() {
+        let origin: Vector<3, Fraction<i64>> =
+            Vector::new([0, 0, 0]).map(Fraction::from);
+        let velocity = Vector::new([2, 4, 6]).map(Fraction::from);
+        let halfway = Vector::new([
+            Fraction::from(1),
+            Fraction::from(2),
+            Fraction::from(3),
+        ]);
+        assert!(is_point_ahead(origin, velocity, halfway));
+        assert!(!is_point_ahead(origin, velocity, halfway.map(|x| -x)));
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        let a: Vector<2, i64> = Vector::new([3, 1]);
+        assert_eq!(a.rotate_cw_90(), Vector::new([1, -3]));
+        assert_eq!(a.rotate_ccw_90(), Vector::new([-1, 3]));
+        assert_eq!(a.rotate_ccw_90(), Matrix::rotate() * a);
+        assert_eq!(a.rotate_cw_90().rotate_ccw_90(), a);
+        assert_eq!(
+            a.rotate_cw_90()
+                .rotate_cw_90()
+                .rotate_cw_90()
+                .rotate_cw_90(),
+            a
+        );
+    }
+
+    #[test]
+    fn test_vector4_accessors() {
+        let a: Vector<4, i64> = Vector::new([1, -2, 3, 4]);
+        assert_eq!(a.x(), 1);
+        assert_eq!(a.y(), -2);
+        assert_eq!(a.z(), 3);
+        assert_eq!(a.w(), 4);
+    }
+
+    #[test]
+    fn test_homogeneous_transform() {
+        let point: Vector<3, i64> = Vector::new([1, 2, 3]);
+        let translate = Matrix::translation(Vector::new([10, 0, -5]));
+        let rotate = Matrix::from_linear(Matrix::rotate_z());
+
+        // Translate to (11, 2, -2), then rotate about the z axis.
+        let transform = rotate * translate;
+        let result =
+            Vector::from_homogeneous(transform * point.to_homogeneous());
+        assert_eq!(result, Vector::new([-2, 11, -2]));
+
+        assert_eq!(
+            result,
+            Matrix::rotate_z() * (point + Vector::new([10, 0, -5]))
+        );
+    }
+
+    #[test]
+    fn test_triangle_area2() {
+        let a = Vector::new([0, 0]);
+        let b = Vector::new([4, 0]);
+        let c = Vector::new([0, 3]);
+        assert_eq!(triangle_area2(a, b, c), 12);
+        assert_eq!(triangle_area2(a, c, b), -12);
+        assert_eq!(triangle_area2(b, c, a), 12);
+        assert_eq!(triangle_area2(a, b, Vector::new([8, 0])), 0);
+        assert_eq!(
+            triangle_area2(
+                Vector::new([1, 1]),
+                Vector::new([2, 2]),
+                Vector::new([-3, -3])
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        // An L-shaped polygon
+        //   ####.
+        //   #..#.
+        //   #..###
+        //   #....#
+        //   ######
+        let polygon: Vec<Vector<2, i64>> =
+            [[0, 0], [3, 0], [3, 2], [5, 2], [5, 4], [0, 4]]
+                .into_iter()
+                .map(Vector::new)
+                .collect();
+        let inside =
+            |x: i64, y: i64| point_in_polygon(Vector::new([x, y]), &polygon);
+
+        // Inside
+        assert!(inside(1, 1));
+        assert!(inside(2, 3));
+        assert!(inside(4, 3));
+
+        // Outside
+        assert!(!inside(4, 1));
+        assert!(!inside(6, 3));
+        assert!(!inside(-1, 2));
+        assert!(!inside(4, 0));
+        assert!(!inside(1, 5));
+
+        // On the boundary
+        assert!(inside(0, 0));
+        assert!(inside(2, 0));
+        assert!(inside(3, 1));
+        assert!(inside(4, 2));
+        assert!(inside(5, 4));
+    }
+
+    #[test]
+    fn test_matrix_map() {
+        let a: Matrix<2, 2, i64> = Matrix::new([[1, 2], [3, 4]]);
+        let b: Matrix<2, 2, Fraction<i64>> = a.map(Fraction::from);
+        assert_eq!(
+            b,
+            Matrix::new([
+                [Fraction::from(1), Fraction::from(2)],
+                [Fraction::from(3), Fraction::from(4)],
+            ])
+        );
+        assert_eq!(
+            b.map(|frac| frac * Fraction::new(1, 2)),
+            Matrix::new([
+                [Fraction::new(1, 2), Fraction::from(1)],
+                [Fraction::new(3, 2), Fraction::from(2)],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_vector_mag2() {
+        let a: Vector<3, i64> = Vector::new([1, -2, 3]);
+        assert_eq!(a.mag2(), 14);
+        assert_eq!(a.mag2(), a.dot_product(a));
+        assert_eq!(a.mag2(), a.dist2(&Vector::zero()));
+        assert_eq!(Vector::<3, i64>::zero().mag2(), 0);
+    }
+
+    #[test]
+    fn test_vector_dot_product() {
+        let a: Vector<3, i64> = Vector::new([1, 2, 3]);
+        let b: Vector<3, i64> = Vector::new([4, -5, 6]);
+        assert_eq!(a.dot_product(b), 4 - 10 + 18);
+    }
+
+    #[test]
+    fn test_vector_component_min_max() {
+        let a = Vector::new([1, 5, -3]);
+        let b = Vector::new([2, 4, -6]);
+        assert_eq!(a.component_min(b), Vector::new([1, 4, -6]));
+        assert_eq!(a.component_max(b), Vector::new([2, 5, -3]));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [
+            Vector::new([1, 5, -3]),
+            Vector::new([2, 4, -6]),
+            Vector::new([0, 4, 0]),
+        ];
+        let bbox = BoundingBox::from_points(points).unwrap();
+        assert_eq!(bbox.min, Vector::new([0, 4, -6]));
+        assert_eq!(bbox.max, Vector::new([2, 5, 0]));
+        assert_eq!(bbox.shape(), Vector::new([3, 2, 7]));
+        assert!(points.iter().all(|point| bbox.contains(point)));
+        assert!(!bbox.contains(&Vector::new([3, 4, 0])));
+
+        assert!(BoundingBox::<2>::from_points(std::iter::empty()).is_none());
+    }
+
     #[test]
     fn test_matrix_add() {
         let a = Matrix::new([[0, 1], [2, 3], [4, 5]]);
@@ -799,4 +1648,133 @@ mod tests {
         let c = Vector::<3>::new([20, 10 * 2 + 20 * 3, 10 * 4 + 20 * 5]);
         assert_eq!(a * b, c);
     }
+
+    #[test]
+    fn test_matrix_pow() {
+        let a = Matrix::new([[1, 1, 0], [1, 0, 2], [0, -1, 1]]);
+        (0..20).for_each(|power| {
+            let naive =
+                (0..power).fold(Matrix::identity(), |cum_prod, _| a * cum_prod);
+            assert_eq!(a.pow(power), naive);
+        });
+    }
+
+    #[test]
+    fn test_matrix_pow_fibonacci() {
+        // [[1,1],[1,0]]^n == [[F(n+1), F(n)], [F(n), F(n-1)]]
+        let fib = Matrix::<2, 2>::new([[1, 1], [1, 0]]);
+        assert_eq!(fib.pow(90)[(0, 1)], 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn test_matrix_from_columns() {
+        let cols = [[1, 4], [2, 5], [3, 6]];
+        let from_cols = Matrix::<2, 3>::from_columns(cols);
+        assert_eq!(from_cols, Matrix::new([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(from_cols, Matrix::<3, 2>::new(cols).transpose());
+        assert_eq!(from_cols[(1, 0)], 4);
+
+        // The columns of a transformation are the images of the
+        // basis vectors.
+        let rotation = Matrix::<3, 3>::from_columns([
+            Vector::new([0, 1, 0]),
+            Vector::new([-1, 0, 0]),
+            Vector::new([0, 0, 1]),
+        ]);
+        assert_eq!(rotation * Vector::new([1, 0, 0]), Vector::new([0, 1, 0]));
+        assert_eq!(rotation * Vector::new([0, 1, 0]), Vector::new([-1, 0, 0]));
+    }
+
+    #[test]
+    fn test_matrix_is_symmetric() {
+        let a = Matrix::new([[1, 2, 3], [2, 4, 5], [3, 5, 6]]);
+        assert!(a.is_symmetric());
+        assert!(Matrix::<3, 3>::identity().is_symmetric());
+
+        let b = Matrix::new([[1, 2, 3], [2, 4, 5], [3, -5, 6]]);
+        assert!(!b.is_symmetric());
+        assert!(!Matrix::<3, 3>::rotate_x().is_symmetric());
+    }
+
+    #[test]
+    fn test_matrix_is_diagonal() {
+        let a = Matrix::new([[2, 0, 0], [0, -1, 0], [0, 0, 0]]);
+        assert!(a.is_diagonal());
+        assert!(a.is_symmetric());
+        assert!(Matrix::<3, 3>::zero().is_diagonal());
+
+        let b = Matrix::new([[2, 0, 0], [0, -1, 0], [1, 0, 0]]);
+        assert!(!b.is_diagonal());
+        assert!(!Matrix::new([[1, 2, 3], [2, 4, 5], [3, 5, 6]]).is_diagonal());
+    }
+
+    #[test]
+    fn test_solve_unique_system() {
+        // x + y = 3
+        // x - y = 1
+        let system = LinearSystem::new(
+            Matrix::new([[1, 1], [1, -1]]),
+            Vector::new([3, 1]),
+        );
+        let solution = system.solve().unwrap();
+        assert_eq!(
+            solution.offset(),
+            &Vector::new([Fraction::from(2), Fraction::from(1)])
+        );
+        assert!(solution.basis_states().is_empty());
+    }
+
+    #[test]
+    fn test_solve_fractional_system() {
+        // 2x = 1
+        // 3y = 2
+        let system = LinearSystem::new(
+            Matrix::new([[2, 0], [0, 3]]),
+            Vector::new([1, 2]),
+        );
+        let solution = system.solve().unwrap();
+        assert_eq!(
+            solution.offset(),
+            &Vector::new([Fraction::new(1, 2), Fraction::new(2, 3)])
+        );
+    }
+
+    #[test]
+    fn test_solve_inconsistent_system() {
+        // x + y = 1
+        // x + y = 2
+        let system = LinearSystem::new(
+            Matrix::new([[1, 1], [1, 1]]),
+            Vector::new([1, 2]),
+        );
+        assert!(system.solve().is_none());
+    }
+
+    #[test]
+    fn test_solve_underconstrained_system() {
+        // x + 2y - z = 4
+        //   2y + 2z = 6
+        let matrix = Matrix::new([[1, 2, -1], [0, 2, 2]]);
+        let system = LinearSystem::new(matrix, Vector::new([4, 6]));
+        let solution = system.solve().unwrap();
+
+        let apply = |vec: &Vector<3, Fraction>| -> Vector<2, Fraction> {
+            std::array::from_fn(|i| {
+                (0..3)
+                    .map(|j| Fraction::from(matrix[(i, j)]) * vec[j])
+                    .sum()
+            })
+            .into()
+        };
+
+        assert_eq!(
+            apply(solution.offset()),
+            Vector::new([4, 6]).map(Fraction::from)
+        );
+        assert_eq!(solution.basis_states().len(), 1);
+        solution.basis_states().iter().for_each(|basis| {
+            assert!(!basis.is_zero());
+            assert!(apply(basis).is_zero());
+        });
+    }
 }
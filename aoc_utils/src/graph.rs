@@ -1,11 +1,14 @@
 use std::cmp::Reverse;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 use itertools::Itertools;
 use priority_queue::PriorityQueue;
 use thiserror::Error;
 
+use crate::UnionFind;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Target is not reachable")]
@@ -14,6 +17,10 @@ pub enum Error {
     InvalidReverseIndex,
     #[error("Back-tracking along path found loop")]
     CircularReversePath,
+    #[error("Graph contains a cycle with negative total weight")]
+    NegativeCycle,
+    #[error("Graph contains a cycle")]
+    GraphHasCycle,
 }
 
 pub trait DynamicGraphNode: Eq + Hash {}
@@ -136,6 +143,393 @@ pub trait DirectedGraph<T> {
             Some(visiting)
         })
     }
+
+    /// Iterate over all states reachable from the initial states, in
+    /// the same order as `iter_depth_first`.  Each state is returned
+    /// along with the path by which it was first reached, starting
+    /// with one of the initial states and ending with the state
+    /// itself.
+    fn iter_depth_first_paths<'a>(
+        &'a self,
+        initial: impl IntoIterator<Item = T>,
+    ) -> impl Iterator<Item = (T, Vec<T>)> + 'a
+    where
+        T: 'a,
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut to_visit = Vec::new();
+        let mut seen = HashSet::new();
+
+        for initial in initial.into_iter() {
+            if seen.insert(initial.clone()) {
+                to_visit.push(vec![initial]);
+            }
+        }
+
+        std::iter::from_fn(move || {
+            let path = to_visit.pop()?;
+            let visiting = path.last().expect("Paths are never empty").clone();
+
+            for node in self.connections_from(&visiting) {
+                if !seen.contains(&node) {
+                    seen.insert(node.clone());
+                    let mut new_path = path.clone();
+                    new_path.push(node);
+                    to_visit.push(new_path);
+                }
+            }
+
+            Some((visiting, path))
+        })
+    }
+
+    /// Iterate over all states reachable from the initial states, in
+    /// breadth-first order.  The `total_dist` of each item is the
+    /// number of edges along the shortest path from an initial state.
+    fn iter_breadth_first<'a>(
+        &'a self,
+        initial: impl IntoIterator<Item = T>,
+    ) -> impl Iterator<Item = SearchItem<T>> + 'a
+    where
+        T: 'a,
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut to_visit = VecDeque::new();
+        let mut seen = HashSet::new();
+
+        for initial in initial.into_iter() {
+            if seen.insert(initial.clone()) {
+                to_visit.push_back(SearchItem {
+                    item: initial,
+                    total_dist: 0,
+                    backref: None,
+                });
+            }
+        }
+
+        let mut index = 0;
+        std::iter::from_fn(move || {
+            let visiting = to_visit.pop_front()?;
+
+            for node in self.connections_from(&visiting.item) {
+                if !seen.contains(&node) {
+                    seen.insert(node.clone());
+                    to_visit.push_back(SearchItem {
+                        item: node,
+                        total_dist: visiting.total_dist + 1,
+                        backref: Some(index),
+                    });
+                }
+            }
+
+            index += 1;
+            Some(visiting)
+        })
+    }
+
+    /// Iterate over the layers of a breadth-first search.  The first
+    /// layer contains the initial states, and each subsequent layer
+    /// contains the states first reached at the next distance.  Ends
+    /// once no new states are reachable.
+    fn bfs_layers<'a>(
+        &'a self,
+        initial: impl IntoIterator<Item = T>,
+    ) -> impl Iterator<Item = Vec<T>> + 'a
+    where
+        T: 'a,
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        let first: Vec<T> = initial
+            .into_iter()
+            .filter(|node| seen.insert(node.clone()))
+            .collect();
+
+        std::iter::successors(
+            (!first.is_empty()).then_some(first),
+            move |layer: &Vec<T>| {
+                let next: Vec<T> = layer
+                    .iter()
+                    .flat_map(|node| self.connections_from(node))
+                    .filter(|node| seen.insert(node.clone()))
+                    .collect();
+                (!next.is_empty()).then_some(next)
+            },
+        )
+    }
+
+    /// Count the number of distinct paths from the initial node to any
+    /// node that satisfies `is_target`.  A path ends upon reaching a
+    /// target.  Returns `Error::GraphHasCycle` if a cycle is reachable
+    /// from the initial node, as there would be infinitely many
+    /// paths.
+    fn count_paths(
+        &self,
+        initial: T,
+        is_target: impl Fn(&T) -> bool,
+    ) -> Result<u64, Error>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        enum Visit<T> {
+            Enter(T),
+            Exit(T),
+        }
+
+        let mut num_paths: HashMap<T, u64> = HashMap::new();
+        let mut in_progress: HashSet<T> = HashSet::new();
+        let mut to_visit = vec![Visit::Enter(initial.clone())];
+
+        while let Some(visit) = to_visit.pop() {
+            match visit {
+                Visit::Enter(node) => {
+                    if in_progress.contains(&node) {
+                        return Err(Error::GraphHasCycle);
+                    }
+                    let Entry::Vacant(entry) = num_paths.entry(node.clone())
+                    else {
+                        continue;
+                    };
+                    if is_target(&node) {
+                        entry.insert(1);
+                        continue;
+                    }
+
+                    in_progress.insert(node.clone());
+                    let children: Vec<T> =
+                        self.connections_from(&node).collect();
+                    to_visit.push(Visit::Exit(node));
+                    to_visit.extend(children.into_iter().map(Visit::Enter));
+                }
+                Visit::Exit(node) => {
+                    let total = self
+                        .connections_from(&node)
+                        .map(|child| num_paths[&child])
+                        .sum();
+                    in_progress.remove(&node);
+                    num_paths.insert(node, total);
+                }
+            }
+        }
+
+        Ok(num_paths[&initial])
+    }
+
+    /// Group all nodes reachable from the given nodes into strongly
+    /// connected components, using Tarjan's algorithm.  Components
+    /// are returned in reverse topological order, such that no
+    /// component has an edge to a later component.
+    fn strongly_connected_components(
+        &self,
+        nodes: impl IntoIterator<Item = T>,
+    ) -> Vec<Vec<T>>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        struct Frame<T> {
+            node: T,
+            children: Vec<T>,
+            next_child: usize,
+        }
+
+        let mut index: HashMap<T, usize> = HashMap::new();
+        let mut lowlink: HashMap<T, usize> = HashMap::new();
+        let mut on_stack: HashSet<T> = HashSet::new();
+        let mut component_stack: Vec<T> = Vec::new();
+        let mut components: Vec<Vec<T>> = Vec::new();
+
+        for root in nodes {
+            if index.contains_key(&root) {
+                continue;
+            }
+
+            let mut call_stack: Vec<Frame<T>> = Vec::new();
+            let mut to_enter = Some(root);
+
+            loop {
+                if let Some(node) = to_enter.take() {
+                    let node_index = index.len();
+                    index.insert(node.clone(), node_index);
+                    lowlink.insert(node.clone(), node_index);
+                    on_stack.insert(node.clone());
+                    component_stack.push(node.clone());
+                    call_stack.push(Frame {
+                        children: self.connections_from(&node).collect(),
+                        node,
+                        next_child: 0,
+                    });
+                }
+
+                let Some(frame) = call_stack.last_mut() else {
+                    break;
+                };
+
+                if let Some(child) = frame.children.get(frame.next_child) {
+                    frame.next_child += 1;
+                    if !index.contains_key(child) {
+                        to_enter = Some(child.clone());
+                    } else if on_stack.contains(child) {
+                        let low = lowlink[&frame.node].min(index[child]);
+                        lowlink.insert(frame.node.clone(), low);
+                    }
+                    continue;
+                }
+
+                let frame = call_stack.pop().unwrap();
+                let node_low = lowlink[&frame.node];
+                if let Some(parent) = call_stack.last() {
+                    let low = lowlink[&parent.node].min(node_low);
+                    lowlink.insert(parent.node.clone(), low);
+                }
+
+                if node_low == index[&frame.node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = component_stack.pop().unwrap();
+                        on_stack.remove(&member);
+                        let is_root = member == frame.node;
+                        component.push(member);
+                        if is_root {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Order the given nodes such that every edge between them points
+    /// from an earlier node to a later node, using Kahn's algorithm.
+    /// Edges to nodes outside of the given set are ignored.  Returns
+    /// `Error::GraphHasCycle` if no such ordering exists.
+    fn topological_sort(
+        &self,
+        nodes: impl IntoIterator<Item = T>,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        // Duplicates would have their in-degree counted more than
+        // once, so each node is only kept once.
+        let nodes: Vec<T> = nodes.into_iter().unique().collect();
+        let mut num_incoming: HashMap<T, usize> =
+            nodes.iter().map(|node| (node.clone(), 0)).collect();
+        for node in &nodes {
+            for new_node in self.connections_from(node) {
+                if let Some(count) = num_incoming.get_mut(&new_node) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut to_visit: VecDeque<T> = nodes
+            .iter()
+            .filter(|node| num_incoming[node] == 0)
+            .cloned()
+            .collect();
+
+        let mut sorted = Vec::with_capacity(nodes.len());
+        while let Some(node) = to_visit.pop_front() {
+            for new_node in self.connections_from(&node) {
+                if let Some(count) = num_incoming.get_mut(&new_node) {
+                    *count -= 1;
+                    if *count == 0 {
+                        to_visit.push_back(new_node);
+                    }
+                }
+            }
+            sorted.push(node);
+        }
+
+        if sorted.len() == num_incoming.len() {
+            Ok(sorted)
+        } else {
+            Err(Error::GraphHasCycle)
+        }
+    }
+
+    /// Find a path that traverses every edge leaving the given nodes
+    /// exactly once, using Hierholzer's algorithm.  The path is a
+    /// circuit if every node has equal in-degree and out-degree.
+    /// Returns `None` if there are no edges, if the degrees do not
+    /// permit an Eulerian path, or if the edges are not connected.
+    fn eulerian_path(
+        &self,
+        nodes: impl IntoIterator<Item = T>,
+    ) -> Option<Vec<T>>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut remaining_edges: HashMap<T, Vec<T>> = nodes
+            .into_iter()
+            .map(|node| {
+                let edges = self.connections_from(&node).collect();
+                (node, edges)
+            })
+            .collect();
+
+        let mut balance: HashMap<T, i64> = HashMap::new();
+        for (node, edges) in &remaining_edges {
+            *balance.entry(node.clone()).or_default() += edges.len() as i64;
+            for to in edges {
+                *balance.entry(to.clone()).or_default() -= 1;
+            }
+        }
+        let num_edges: usize = remaining_edges.values().map(Vec::len).sum();
+        if num_edges == 0 {
+            return None;
+        }
+
+        let mut start = None;
+        let mut has_end = false;
+        for (node, &diff) in &balance {
+            match diff {
+                0 => {}
+                1 if start.is_none() => {
+                    start = Some(node.clone());
+                }
+                -1 if !has_end => {
+                    has_end = true;
+                }
+                _ => {
+                    return None;
+                }
+            }
+        }
+        let start = start.unwrap_or_else(|| {
+            remaining_edges
+                .iter()
+                .find(|(_, edges)| !edges.is_empty())
+                .map(|(node, _)| node.clone())
+                .unwrap()
+        });
+
+        let mut stack = vec![start];
+        let mut path = Vec::with_capacity(num_edges + 1);
+        while let Some(node) = stack.last() {
+            match remaining_edges.get_mut(node).and_then(|edges| edges.pop()) {
+                Some(next) => stack.push(next),
+                None => path.push(stack.pop().unwrap()),
+            }
+        }
+
+        // Any unvisited edges belong to a disconnected component.
+        if path.len() != num_edges + 1 {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
 }
 
 ///
@@ -250,30 +644,561 @@ pub trait EdgeWeightedGraph<T> {
             let index = finished.len();
             finished.insert(item.clone());
 
-            self.connections_from(&item)
-                .filter(|(new_item, _)| !finished.contains(new_item))
-                .filter_map(|(new_item, edge_weight)| {
-                    let heuristic_to_dest = heuristic_func(&new_item)?;
-                    Some((
-                        new_item,
-                        SearchItemMetadata {
-                            total_dist: total_dist + edge_weight,
-                            backref: Some(index),
-                            heuristic_to_dest,
-                        },
-                    ))
-                })
-                .for_each(|(new_item, info)| {
-                    search_queue.push_increase(new_item, info);
-                });
+            self.connections_from(&item)
+                .filter(|(new_item, _)| !finished.contains(new_item))
+                .filter_map(|(new_item, edge_weight)| {
+                    let heuristic_to_dest = heuristic_func(&new_item)?;
+                    Some((
+                        new_item,
+                        SearchItemMetadata {
+                            total_dist: total_dist + edge_weight,
+                            backref: Some(index),
+                            heuristic_to_dest,
+                        },
+                    ))
+                })
+                .for_each(|(new_item, info)| {
+                    search_queue.push_increase(new_item, info);
+                });
+
+            Some(SearchItem {
+                item,
+                total_dist,
+                backref,
+            })
+        })
+    }
+
+    /// Run an A* search, stopping at the first node that satisfies
+    /// `is_goal`.  Returns that node along with its distance from the
+    /// initial node(s), or None if no goal is reachable.  So long as
+    /// the heuristic never overestimates the remaining distance, the
+    /// goal returned is the closest one.
+    fn a_star_to<F>(
+        &self,
+        initial: impl IntoIterator<Item = T>,
+        heuristic_func: F,
+        is_goal: impl Fn(&T) -> bool,
+    ) -> Option<(T, u64)>
+    where
+        F: FnMut(&T) -> Option<u64>,
+        T: Clone,
+        T: Eq + Hash,
+    {
+        self.iter_a_star(initial, heuristic_func)
+            .find(|search_item| is_goal(&search_item.item))
+            .map(|search_item| (search_item.item, search_item.total_dist))
+    }
+
+    /// Find the shortest path from the initial node(s) to the nearest
+    /// node that satisfies `is_target`.  The path includes the target
+    /// but not the initial node, and each node is paired with the
+    /// weight of the edge used to reach it.  Returns None if no target
+    /// is reachable.
+    fn shortest_path_to(
+        &self,
+        initial: impl IntoIterator<Item = T>,
+        is_target: impl Fn(&T) -> bool,
+    ) -> Option<Vec<(T, u64)>>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut visited: Vec<SearchItem<T>> = Vec::new();
+        for search_item in self.iter_dijkstra(initial) {
+            let found_target = is_target(&search_item.item);
+            visited.push(search_item);
+            if found_target {
+                break;
+            }
+        }
+
+        if !visited.last().is_some_and(|last| is_target(&last.item)) {
+            return None;
+        }
+
+        let mut path: Vec<(T, u64)> =
+            std::iter::successors(visited.last(), |search_item| {
+                search_item.backref.map(|index| &visited[index])
+            })
+            .tuple_windows()
+            .map(|(after, before)| {
+                (after.item.clone(), after.total_dist - before.total_dist)
+            })
+            .collect();
+        path.reverse();
+        Some(path)
+    }
+
+    /// Find the goal closest to the initial node, along with its
+    /// distance.  If several goals are equally close, returns
+    /// whichever is reached first.  Returns `None` if no goal is
+    /// reachable.
+    fn nearest_goal(&self, initial: T, goals: &HashSet<T>) -> Option<(T, u64)>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        self.nearest_goal_by_key(initial, goals, |_| ())
+    }
+
+    /// Same as `nearest_goal`, but breaks ties between equally-close
+    /// goals by selecting the goal with the smallest key.  For
+    /// example, a key of `(y, x)` selects the first goal in reading
+    /// order.
+    fn nearest_goal_by_key<K: Ord>(
+        &self,
+        initial: T,
+        goals: &HashSet<T>,
+        key: impl Fn(&T) -> K,
+    ) -> Option<(T, u64)>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut search = self.iter_dijkstra([initial]);
+        let first =
+            search.find(|search_item| goals.contains(&search_item.item))?;
+        let dist = first.total_dist;
+
+        std::iter::once(first)
+            .chain(
+                search
+                    .take_while(|search_item| search_item.total_dist == dist)
+                    .filter(|search_item| goals.contains(&search_item.item)),
+            )
+            .min_by_key(|search_item| key(&search_item.item))
+            .map(|search_item| (search_item.item, dist))
+    }
+
+    /// Contract the graph down to the nodes for which `keep` returns
+    /// true, returning the length of the shortest path between each
+    /// pair of kept nodes.  Paths may pass through any node, kept or
+    /// not.  Only nodes reachable from the initial node(s) are
+    /// considered.  The initial nodes are always included as the
+    /// start of a path, even if they are not kept, so that the
+    /// contracted graph can be entered from them.  Pairs for which no
+    /// path exists are absent from the result, as are paths from a
+    /// node to itself.
+    fn contract_to(
+        &self,
+        initial: impl IntoIterator<Item = T>,
+        keep: impl Fn(&T) -> bool,
+    ) -> HashMap<(T, T), u64>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let initial: Vec<T> = initial.into_iter().collect();
+        let kept: Vec<T> = self
+            .iter_dijkstra(initial.iter().cloned())
+            .map(|search_item| search_item.item)
+            .filter(|node| keep(node))
+            .collect();
+
+        initial
+            .into_iter()
+            .chain(kept)
+            .unique()
+            .flat_map(|source| {
+                self.iter_dijkstra([source.clone()])
+                    .filter(|search_item| {
+                        search_item.item != source && keep(&search_item.item)
+                    })
+                    .map(|search_item| {
+                        (
+                            (source.clone(), search_item.item),
+                            search_item.total_dist,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Find the minimum distance between every pair of nodes in the
+    /// given set, using the Floyd-Warshall algorithm.  Only edges
+    /// between nodes within the set are considered.  As with
+    /// `contract_to`, pairs for which no path exists are absent from
+    /// the result, as are paths from a node to itself.
+    fn all_pairs_shortest_paths(&self, nodes: &[T]) -> HashMap<(T, T), u64>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let index_lookup: HashMap<&T, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node, i))
+            .collect();
+
+        let n = nodes.len();
+        let mut dist: Vec<Vec<Option<u64>>> = vec![vec![None; n]; n];
+        for (i, node) in nodes.iter().enumerate() {
+            dist[i][i] = Some(0);
+            for (new_node, edge_weight) in self.connections_from(node) {
+                if let Some(&j) = index_lookup.get(&new_node) {
+                    dist[i][j] = Some(
+                        dist[i][j].map_or(edge_weight, |d| d.min(edge_weight)),
+                    );
+                }
+            }
+        }
+
+        for k in 0..n {
+            let from_k = dist[k].clone();
+            for from_i in dist.iter_mut() {
+                let Some(i_to_k) = from_i[k] else {
+                    continue;
+                };
+                for (i_to_j, k_to_j) in from_i.iter_mut().zip(&from_k) {
+                    if let Some(k_to_j) = k_to_j {
+                        let through_k = i_to_k + k_to_j;
+                        if i_to_j.is_none_or(|d| through_k < d) {
+                            *i_to_j = Some(through_k);
+                        }
+                    }
+                }
+            }
+        }
+
+        (0..n)
+            .cartesian_product(0..n)
+            .filter(|(i, j)| nodes[*i] != nodes[*j])
+            .filter_map(|(i, j)| {
+                dist[i][j].map(|d| ((nodes[i].clone(), nodes[j].clone()), d))
+            })
+            .collect()
+    }
+
+    /// Find a minimum spanning tree (or forest, if the nodes are not
+    /// all connected) over the given nodes, using Kruskal's
+    /// algorithm.  Only edges between nodes within the set are
+    /// considered, and the direction of each edge is ignored.
+    /// Returns the edges that make up the tree, along with their
+    /// weights.
+    fn minimum_spanning_tree(
+        &self,
+        nodes: impl IntoIterator<Item = T>,
+    ) -> Vec<(T, T, u64)>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let nodes: Vec<T> = nodes.into_iter().collect();
+        let index_lookup: HashMap<&T, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node, i))
+            .collect();
+
+        let edges = nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, node)| {
+                self.connections_from(node)
+                    .filter_map(|(new_node, edge_weight)| {
+                        let j = *index_lookup.get(&new_node)?;
+                        Some((i, j, edge_weight))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .sorted_by_key(|&(_, _, edge_weight)| edge_weight);
+
+        let mut sets = UnionFind::new(nodes.len());
+        edges
+            .filter(|&(i, j, _)| sets.union(i, j))
+            .map(|(i, j, edge_weight)| {
+                (nodes[i].clone(), nodes[j].clone(), edge_weight)
+            })
+            .collect()
+    }
+
+    /// Find the maximum total distance of any path from the initial
+    /// node to the target node.  Returns `Ok(None)` if the target is
+    /// unreachable, and `Error::GraphHasCycle` if the nodes reachable
+    /// from the initial node contain a cycle.
+    fn longest_path_dag(
+        &self,
+        initial: T,
+        target: T,
+    ) -> Result<Option<u64>, Error>
+    where
+        T: Clone,
+        T: Eq + Hash,
+        Self: Sized,
+    {
+        let unweighted = AsDynamicGraph(self);
+        let reachable = unweighted.iter_depth_first([initial.clone()]);
+        let order = unweighted.topological_sort(reachable)?;
+
+        let mut longest: HashMap<T, u64> = HashMap::new();
+        longest.insert(initial, 0);
+        for node in order {
+            let Some(&dist_to_node) = longest.get(&node) else {
+                continue;
+            };
+            for (new_node, edge_weight) in self.connections_from(&node) {
+                let new_dist = dist_to_node + edge_weight;
+                longest
+                    .entry(new_node)
+                    .and_modify(|dist| *dist = (*dist).max(new_dist))
+                    .or_insert(new_dist);
+            }
+        }
+
+        Ok(longest.get(&target).copied())
+    }
+}
+
+// Adapter to expose an `EdgeWeightedGraph` as a `DynamicGraph`, and
+// therefore as a `DirectedGraph`, to re-use the unweighted
+// algorithms.
+struct AsDynamicGraph<'a, Graph>(&'a Graph);
+
+impl<'g, T, Graph> DynamicGraph<T> for AsDynamicGraph<'g, Graph>
+where
+    T: DynamicGraphNode,
+    Graph: EdgeWeightedGraph<T>,
+{
+    fn connections_from(&self, node: &T) -> Vec<(T, u64)> {
+        self.0.connections_from(node).collect()
+    }
+}
+
+pub trait SignedEdgeWeightedGraph<T> {
+    /// Given a node, return all nodes directly accessible from that
+    /// node, along with the cost associated with each edge.  Unlike
+    /// `EdgeWeightedGraph`, the cost of an edge may be negative.
+    fn connections_from<'a>(
+        &'a self,
+        node: &'a T,
+    ) -> impl Iterator<Item = (T, i64)> + 'a;
+
+    /// Find the minimum distance from the initial node to every
+    /// reachable node, using the Bellman-Ford algorithm.  The
+    /// `num_nodes_hint` is only used to pre-allocate the result.
+    /// Returns `Error::NegativeCycle` if a cycle with negative total
+    /// weight is reachable from the initial node.
+    fn bellman_ford(
+        &self,
+        initial: T,
+        num_nodes_hint: usize,
+    ) -> Result<HashMap<T, i64>, Error>
+    where
+        T: Clone,
+        T: Eq + Hash,
+    {
+        let mut distances = HashMap::with_capacity(num_nodes_hint);
+        distances.insert(initial, 0);
+
+        // After N rounds of relaxation, the distances include every
+        // path of up to N edges.  If a round still finds a shorter
+        // path after there have been as many rounds as there are
+        // known nodes, then that path must loop through a negative
+        // cycle.
+        for num_rounds in 1.. {
+            let mut updated = false;
+            let nodes: Vec<T> = distances.keys().cloned().collect();
+            for node in nodes {
+                let dist_to_node = distances[&node];
+                for (new_node, edge_weight) in self.connections_from(&node) {
+                    let new_dist = dist_to_node + edge_weight;
+                    let is_shorter = distances
+                        .get(&new_node)
+                        .is_none_or(|&prev_dist| new_dist < prev_dist);
+                    if is_shorter {
+                        distances.insert(new_node, new_dist);
+                        updated = true;
+                    }
+                }
+            }
+
+            if !updated {
+                break;
+            } else if num_rounds >= distances.len() {
+                return Err(Error::NegativeCycle);
+            }
+        }
+
+        Ok(distances)
+    }
+}
+
+// Search outward from the initial node until the target node is
+// finalized.  On success, returns all finalized nodes, along with the
+// cost of the shortest path to the target.
+fn search_until_target<T, Graph>(
+    graph: &Graph,
+    initial: T,
+    target: &T,
+) -> Result<(HashMap<T, InternalInfo>, u64), SearchResult<T>>
+where
+    T: DynamicGraphNode,
+    Graph: DynamicGraph<T> + ?Sized,
+{
+    let get_heuristic =
+        |pos: &T| -> Option<u64> { graph.heuristic_between(pos, target) };
+
+    let mut search_queue: PriorityQueue<T, InternalInfo> = PriorityQueue::new();
+
+    if let Some(initial_heuristic) = get_heuristic(&initial) {
+        let initial_info = InternalInfo {
+            node_index: None,
+            initial_to_node: 0,
+            heuristic: initial_heuristic,
+            backref: None,
+            num_out_edges: 0,
+        };
+        search_queue.push(initial, initial_info);
+    } else {
+        return Err(SearchResult::HeuristicFailsOnStartPoint);
+    }
+
+    let mut finalized_nodes: HashMap<T, InternalInfo> = HashMap::new();
+    let mut found_target = false;
+    let mut target_cost = 0;
+
+    while !search_queue.is_empty() {
+        let (node, mut info) = search_queue.pop().unwrap();
+
+        let src_to_node = info.initial_to_node;
+        found_target = &node == target;
+        let connected_nodes = graph.connections_from(&node);
+
+        let node_index = finalized_nodes.len();
+        info.node_index = Some(node_index);
+        info.num_out_edges = connected_nodes.len();
+
+        finalized_nodes.insert(node, info);
+
+        if found_target {
+            target_cost = src_to_node;
+            break;
+        }
 
-            Some(SearchItem {
-                item,
-                total_dist,
-                backref,
+        connected_nodes
+            .into_iter()
+            .filter_map(|(new_node, edge_weight)| {
+                get_heuristic(&new_node).map(move |heuristic_to_dest| {
+                    (new_node, edge_weight, heuristic_to_dest)
+                })
             })
-        })
+            .map(|(new_node, edge_weight, heuristic_to_dest)| {
+                let info = InternalInfo {
+                    node_index: None,
+                    initial_to_node: src_to_node + edge_weight,
+                    heuristic: heuristic_to_dest,
+                    backref: Some(GraphEdge {
+                        initial_node: node_index,
+                        edge_weight,
+                    }),
+                    num_out_edges: 0,
+                };
+                (new_node, info)
+            })
+            .filter(|(node, _info)| !finalized_nodes.contains_key(node))
+            .for_each(|(node, info): (T, InternalInfo)| {
+                search_queue.push_increase(node, info);
+            });
+    }
+
+    if !found_target {
+        let reachable = finalized_nodes.into_keys().collect();
+        return Err(SearchResult::NoPathToTarget { reachable });
+    }
+
+    Ok((finalized_nodes, target_cost))
+}
+
+// A single depth-first pass of `DynamicGraph::ida_star`, extending the
+// last node of `path`.  Returns the total cost if a goal is found,
+// leaving the path to the goal in `path`.  Otherwise, returns the
+// smallest cost plus heuristic that exceeded the bound, or None if
+// nothing was pruned and so no goal is reachable.
+fn ida_star_iteration<T, Graph>(
+    graph: &Graph,
+    path: &mut Vec<T>,
+    on_path: &mut HashSet<T>,
+    cost: u64,
+    bound: u64,
+    heuristic: &impl Fn(&T) -> u64,
+    is_goal: &impl Fn(&T) -> bool,
+) -> Result<u64, Option<u64>>
+where
+    T: DynamicGraphNode + Clone,
+    Graph: DynamicGraph<T> + ?Sized,
+{
+    let node = path.last().unwrap().clone();
+    let estimate = cost + heuristic(&node);
+    if estimate > bound {
+        return Err(Some(estimate));
+    }
+    if is_goal(&node) {
+        return Ok(cost);
+    }
+
+    let mut next_bound: Option<u64> = None;
+    for (new_node, edge_weight) in graph.connections_from(&node) {
+        if !on_path.insert(new_node.clone()) {
+            continue;
+        }
+        path.push(new_node);
+        match ida_star_iteration(
+            graph,
+            path,
+            on_path,
+            cost + edge_weight,
+            bound,
+            heuristic,
+            is_goal,
+        ) {
+            Ok(total) => return Ok(total),
+            Err(pruned) => {
+                next_bound = next_bound.into_iter().chain(pruned).min();
+            }
+        }
+        let new_node = path.pop().unwrap();
+        on_path.remove(&new_node);
+    }
+
+    Err(next_bound)
+}
+
+/// Find the cost of the shortest path from `initial` to any node for
+/// which `is_goal` returns true, using Dijkstra's algorithm.  The
+/// graph is defined by the `neighbors` closure, which returns each
+/// node reachable from a node along with the cost of that edge, so
+/// that a quick search doesn't require implementing a graph trait.
+/// Returns `None` if no goal is reachable.
+pub fn dijkstra_fn<S>(
+    initial: S,
+    mut neighbors: impl FnMut(&S) -> Vec<(S, u64)>,
+    is_goal: impl Fn(&S) -> bool,
+) -> Option<u64>
+where
+    S: Eq + Hash + Clone,
+{
+    let mut search_queue: PriorityQueue<S, Reverse<u64>> = PriorityQueue::new();
+    let mut finalized: HashSet<S> = HashSet::new();
+    search_queue.push(initial, Reverse(0));
+
+    while let Some((node, Reverse(cost))) = search_queue.pop() {
+        if !finalized.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(cost);
+        }
+        for (new_node, edge_weight) in neighbors(&node) {
+            if !finalized.contains(&new_node) {
+                search_queue
+                    .push_increase(new_node, Reverse(cost + edge_weight));
+            }
+        }
     }
+
+    None
 }
 
 pub trait DynamicGraph<T: DynamicGraphNode> {
@@ -342,80 +1267,24 @@ pub trait DynamicGraph<T: DynamicGraphNode> {
         }
     }
 
+    // Returns the cost of the shortest path from initial to target,
+    // without reconstructing the path itself.
+    fn shortest_path_length(&self, initial: T, target: T) -> Option<u64> {
+        search_until_target(self, initial, &target)
+            .ok()
+            .map(|(_, cost)| cost)
+    }
+
     fn shortest_path_search_result(
         &self,
         initial: T,
         target: T,
     ) -> SearchResult<T> {
-        let get_heuristic =
-            |pos: &T| -> Option<u64> { self.heuristic_between(pos, &target) };
-
-        let mut search_queue: PriorityQueue<T, InternalInfo> =
-            PriorityQueue::new();
-
-        if let Some(initial_heuristic) = get_heuristic(&initial) {
-            let initial_info = InternalInfo {
-                node_index: None,
-                initial_to_node: 0,
-                heuristic: initial_heuristic,
-                backref: None,
-                num_out_edges: 0,
-            };
-            search_queue.push(initial, initial_info);
-        } else {
-            return SearchResult::HeuristicFailsOnStartPoint;
-        }
-
-        let mut finalized_nodes: HashMap<T, InternalInfo> = HashMap::new();
-        let mut found_target = false;
-
-        while !search_queue.is_empty() {
-            let (node, mut info) = search_queue.pop().unwrap();
-
-            let src_to_node = info.initial_to_node;
-            found_target = node == target;
-            let connected_nodes = self.connections_from(&node);
-
-            let node_index = finalized_nodes.len();
-            info.node_index = Some(node_index);
-            info.num_out_edges = connected_nodes.len();
-
-            finalized_nodes.insert(node, info);
-
-            if found_target {
-                break;
-            }
-
-            connected_nodes
-                .into_iter()
-                .filter_map(|(new_node, edge_weight)| {
-                    get_heuristic(&new_node).map(move |heuristic_to_dest| {
-                        (new_node, edge_weight, heuristic_to_dest)
-                    })
-                })
-                .map(|(new_node, edge_weight, heuristic_to_dest)| {
-                    let info = InternalInfo {
-                        node_index: None,
-                        initial_to_node: src_to_node + edge_weight,
-                        heuristic: heuristic_to_dest,
-                        backref: Some(GraphEdge {
-                            initial_node: node_index,
-                            edge_weight,
-                        }),
-                        num_out_edges: 0,
-                    };
-                    (new_node, info)
-                })
-                .filter(|(node, _info)| !finalized_nodes.contains_key(node))
-                .for_each(|(node, info): (T, InternalInfo)| {
-                    search_queue.push_increase(node, info);
-                });
-        }
-
-        if !found_target {
-            let reachable = finalized_nodes.into_keys().collect();
-            return SearchResult::NoPathToTarget { reachable };
-        }
+        let finalized_nodes = match search_until_target(self, initial, &target)
+        {
+            Ok((finalized_nodes, _)) => finalized_nodes,
+            Err(res) => return res,
+        };
 
         let mut index_lookup: Vec<Option<(T, InternalInfo)>> = finalized_nodes
             .into_iter()
@@ -452,6 +1321,112 @@ pub trait DynamicGraph<T: DynamicGraphNode> {
         }
     }
 
+    // Search outward from the initial node, one step at a time,
+    // keeping only the `beam_width` nodes with the highest score at
+    // each depth.  Returns the path from the initial node to the
+    // highest-scoring goal node at the first depth at which any goal
+    // is found.  This is an approximate search: the optimal path may
+    // be pruned if it passes through low-scoring nodes, and so the
+    // result is not guaranteed to be optimal.
+    fn beam_search(
+        &self,
+        initial: T,
+        beam_width: usize,
+        score: impl Fn(&T) -> i64,
+        is_goal: impl Fn(&T) -> bool,
+    ) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        // Each layer holds the nodes at that depth, along with the
+        // index of the node in the previous layer that reached it.
+        let mut layers: Vec<Vec<(T, Option<usize>)>> = Vec::new();
+        let mut seen: HashSet<T> = HashSet::new();
+
+        seen.insert(initial.clone());
+        let mut beam = vec![(initial, None)];
+
+        while !beam.is_empty() {
+            let goal = beam
+                .iter()
+                .enumerate()
+                .filter(|(_, (node, _))| is_goal(node))
+                .max_by_key(|(_, (node, _))| score(node))
+                .map(|(i, _)| i);
+            layers.push(beam);
+
+            if let Some(goal) = goal {
+                let mut path: Vec<T> = layers
+                    .iter()
+                    .rev()
+                    .scan(Some(goal), |index, layer| {
+                        let (node, backref) = &layer[(*index)?];
+                        *index = *backref;
+                        Some(node.clone())
+                    })
+                    .collect();
+                path.reverse();
+                return Some(path);
+            }
+
+            let prev_layer = layers.last().unwrap();
+            beam = prev_layer
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (node, _))| {
+                    self.connections_from(node)
+                        .into_iter()
+                        .map(move |(new_node, _)| (new_node, Some(i)))
+                })
+                .filter(|(new_node, _)| seen.insert(new_node.clone()))
+                .sorted_by_key(|(new_node, _)| Reverse(score(new_node)))
+                .take(beam_width)
+                .collect();
+        }
+
+        None
+    }
+
+    // Iterative-deepening A* search from the initial node to any node
+    // for which `is_goal` returns true.  Each iteration is a
+    // depth-first search that prunes nodes whose cost plus heuristic
+    // exceeds a bound, raising the bound to the smallest pruned value
+    // for the next iteration.  Memory usage is proportional to the
+    // length of the path, rather than to the number of nodes visited.
+    // The heuristic must not overestimate the remaining cost for the
+    // result to be optimal.  Returns the path, including both the
+    // initial node and the goal, along with its total cost.
+    fn ida_star(
+        &self,
+        initial: T,
+        heuristic: impl Fn(&T) -> u64,
+        is_goal: impl Fn(&T) -> bool,
+    ) -> Option<(Vec<T>, u64)>
+    where
+        T: Clone,
+    {
+        let mut bound = heuristic(&initial);
+        let mut on_path: HashSet<T> =
+            std::iter::once(initial.clone()).collect();
+        let mut path = vec![initial];
+
+        loop {
+            match ida_star_iteration(
+                self,
+                &mut path,
+                &mut on_path,
+                0,
+                bound,
+                &heuristic,
+                &is_goal,
+            ) {
+                Ok(cost) => return Some((path, cost)),
+                Err(Some(next_bound)) => bound = next_bound,
+                Err(None) => return None,
+            }
+        }
+    }
+
     fn dijkstra_search(&self, initial: T) -> DijkstraSearchIter<T, Self>
     where
         T: Clone,
@@ -694,3 +1669,792 @@ impl<
         Some((node, info.into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SignedGraph {
+        edges: Vec<(char, char, i64)>,
+    }
+
+    impl SignedEdgeWeightedGraph<char> for SignedGraph {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a char,
+        ) -> impl Iterator<Item = (char, i64)> + 'a {
+            self.edges
+                .iter()
+                .filter(move |(from, _, _)| from == node)
+                .map(|&(_, to, weight)| (to, weight))
+        }
+    }
+
+    struct UnweightedGraph {
+        edges: Vec<(char, char)>,
+    }
+
+    impl DirectedGraph<char> for UnweightedGraph {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a char,
+        ) -> impl Iterator<Item = char> + 'a {
+            self.edges
+                .iter()
+                .filter(move |(from, _)| from == node)
+                .map(|&(_, to)| to)
+        }
+    }
+
+    struct Maze {
+        x_size: i64,
+        y_size: i64,
+        walls: Vec<(i64, i64)>,
+    }
+
+    impl DirectedGraph<(i64, i64)> for Maze {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a (i64, i64),
+        ) -> impl Iterator<Item = (i64, i64)> + 'a {
+            let (x, y) = *node;
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| {
+                    (0..self.x_size).contains(&x)
+                        && (0..self.y_size).contains(&y)
+                })
+                .filter(|pos| !self.walls.contains(pos))
+        }
+    }
+
+    struct WeightedMaze<'a>(&'a Maze);
+
+    impl EdgeWeightedGraph<(i64, i64)> for WeightedMaze<'_> {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a (i64, i64),
+        ) -> impl Iterator<Item = ((i64, i64), u64)> + 'a {
+            self.0.connections_from(node).map(|pos| (pos, 1))
+        }
+    }
+
+    #[test]
+    fn test_nearest_goal() {
+        let maze = Maze {
+            x_size: 5,
+            y_size: 5,
+            walls: vec![(1, 1)],
+        };
+        let graph = WeightedMaze(&maze);
+
+        let goals: HashSet<(i64, i64)> =
+            [(4, 2), (2, 4), (0, 2), (2, 0), (4, 4)]
+                .into_iter()
+                .collect();
+        let (nearest, dist) = graph.nearest_goal((2, 2), &goals).unwrap();
+        assert_eq!(dist, 2);
+        assert!(nearest != (4, 4));
+
+        let reading_order = |&(x, y): &(i64, i64)| (y, x);
+        assert_eq!(
+            graph.nearest_goal_by_key((2, 2), &goals, reading_order),
+            Some(((2, 0), 2))
+        );
+
+        let goals: HashSet<(i64, i64)> = [(1, 1)].into_iter().collect();
+        assert_eq!(graph.nearest_goal((0, 0), &goals), None);
+    }
+
+    #[test]
+    fn test_iter_depth_first_paths() {
+        let graph = UnweightedGraph {
+            edges: vec![
+                ('a', 'b'),
+                ('a', 'c'),
+                ('b', 'd'),
+                ('c', 'd'),
+                ('d', 'e'),
+                ('e', 'b'),
+                ('f', 'a'),
+            ],
+        };
+        let edges: HashSet<(char, char)> =
+            graph.edges.iter().copied().collect();
+
+        let visited: Vec<(char, Vec<char>)> =
+            graph.iter_depth_first_paths(['a']).collect();
+        assert_eq!(
+            visited
+                .iter()
+                .map(|(node, _)| *node)
+                .sorted()
+                .collect::<String>(),
+            "abcde"
+        );
+        visited.iter().for_each(|(node, path)| {
+            assert_eq!(path.first(), Some(&'a'));
+            assert_eq!(path.last(), Some(node));
+            assert!(path
+                .iter()
+                .tuple_windows()
+                .all(|(&from, &to)| edges.contains(&(from, to))));
+        });
+    }
+
+    #[test]
+    fn test_bfs_layers() {
+        let maze = Maze {
+            x_size: 5,
+            y_size: 5,
+            walls: vec![],
+        };
+
+        let layer_sizes: Vec<usize> =
+            maze.bfs_layers([(0, 0)]).map(|layer| layer.len()).collect();
+        assert_eq!(layer_sizes, vec![1, 2, 3, 4, 5, 4, 3, 2, 1]);
+
+        let layers: Vec<Vec<(i64, i64)>> = maze.bfs_layers([(2, 2)]).collect();
+        let layer_sizes: Vec<usize> =
+            layers.iter().map(|layer| layer.len()).collect();
+        assert_eq!(layer_sizes, vec![1, 4, 8, 8, 4]);
+        assert!(layers.iter().enumerate().all(|(dist, layer)| {
+            layer
+                .iter()
+                .all(|(x, y)| ((x - 2).abs() + (y - 2).abs()) as usize == dist)
+        }));
+    }
+
+    #[test]
+    fn test_iter_breadth_first() {
+        // ..#..
+        // .##.#
+        // .....
+        let maze = Maze {
+            x_size: 5,
+            y_size: 3,
+            walls: vec![(2, 0), (1, 1), (2, 1), (4, 1)],
+        };
+        let items: Vec<_> = maze.iter_breadth_first([(0, 0)]).collect();
+        assert_eq!(items.len(), 11);
+        assert!(items
+            .iter()
+            .tuple_windows()
+            .all(|(a, b)| a.total_dist <= b.total_dist));
+
+        let dist_to = |pos: (i64, i64)| -> u64 {
+            items.iter().find(|x| x.item == pos).unwrap().total_dist
+        };
+        assert_eq!(dist_to((0, 0)), 0);
+        assert_eq!(dist_to((3, 0)), 7);
+        assert_eq!(dist_to((4, 0)), 8);
+        assert_eq!(dist_to((4, 2)), 6);
+
+        let path: Vec<(i64, i64)> = std::iter::successors(
+            items.iter().find(|x| x.item == (4, 0)),
+            |x| x.backref.map(|i| &items[i]),
+        )
+        .map(|x| x.item)
+        .collect();
+        assert_eq!(
+            path,
+            vec![
+                (4, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1),
+                (0, 0)
+            ]
+        );
+    }
+
+    // The caves from the first example of 2021-12-12.  Each state is
+    // the current cave, the set of small caves already visited, and
+    // whether a small cave may still be visited a second time.
+    struct CaveSystem {
+        tunnels: Vec<(&'static str, &'static str)>,
+    }
+
+    type CaveState = (&'static str, Vec<&'static str>, bool);
+
+    impl DirectedGraph<CaveState> for CaveSystem {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a CaveState,
+        ) -> impl Iterator<Item = CaveState> + 'a {
+            let (cave, visited, can_revisit) = node;
+            self.tunnels
+                .iter()
+                .flat_map(|&(a, b)| [(a, b), (b, a)])
+                .filter(move |(from, _)| from == cave)
+                .filter(|(_, to)| *to != "start")
+                .filter_map(move |(_, to)| {
+                    let is_small = to.chars().all(|c| c.is_lowercase());
+                    let is_revisit = is_small && visited.contains(&to);
+                    if is_revisit && !can_revisit {
+                        return None;
+                    }
+                    let mut visited = visited.clone();
+                    if is_small && !is_revisit {
+                        visited.push(to);
+                        visited.sort();
+                    }
+                    Some((to, visited, *can_revisit && !is_revisit))
+                })
+        }
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let caves = CaveSystem {
+            tunnels: vec![
+                ("start", "A"),
+                ("start", "b"),
+                ("A", "c"),
+                ("A", "b"),
+                ("b", "d"),
+                ("A", "end"),
+                ("b", "end"),
+            ],
+        };
+        let is_end = |state: &CaveState| state.0 == "end";
+        assert_eq!(
+            caves.count_paths(("start", vec![], false), is_end).unwrap(),
+            10
+        );
+        assert_eq!(
+            caves.count_paths(("start", vec![], true), is_end).unwrap(),
+            36
+        );
+    }
+
+    #[test]
+    fn test_count_paths_with_cycle() {
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('b', 'c'), ('c', 'b'), ('b', 'd')],
+        };
+        assert!(matches!(
+            graph.count_paths('a', |&node| node == 'd'),
+            Err(Error::GraphHasCycle)
+        ));
+
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd')],
+        };
+        assert_eq!(graph.count_paths('a', |&node| node == 'd').unwrap(), 2);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // Two cycles, a->b->c->a and d->e->d, with an edge from the
+        // first to the second.  The 'f' node is only reachable from
+        // the second cycle.
+        let graph = UnweightedGraph {
+            edges: vec![
+                ('a', 'b'),
+                ('b', 'c'),
+                ('c', 'a'),
+                ('c', 'd'),
+                ('d', 'e'),
+                ('e', 'd'),
+                ('e', 'f'),
+            ],
+        };
+        let components: Vec<Vec<char>> = graph
+            .strongly_connected_components(['a'])
+            .into_iter()
+            .map(|component| component.into_iter().sorted().collect())
+            .collect();
+        assert_eq!(
+            components,
+            vec![vec!['f'], vec!['d', 'e'], vec!['a', 'b', 'c']]
+        );
+
+        let components = graph.strongly_connected_components("fedcba".chars());
+        assert_eq!(components.len(), 3);
+        assert_eq!(components.iter().map(|c| c.len()).sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = UnweightedGraph {
+            edges: vec![
+                ('a', 'c'),
+                ('b', 'c'),
+                ('b', 'd'),
+                ('c', 'e'),
+                ('d', 'e'),
+                ('e', 'f'),
+            ],
+        };
+        let sorted = graph.topological_sort("fedcba".chars()).unwrap();
+        assert_eq!(sorted.len(), 6);
+        let position = |node: char| -> usize {
+            sorted.iter().position(|&n| n == node).unwrap()
+        };
+        for &(from, to) in &graph.edges {
+            assert!(position(from) < position(to));
+        }
+    }
+
+    #[test]
+    fn test_topological_sort_duplicate_nodes() {
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('b', 'c'), ('a', 'c')],
+        };
+        let sorted = graph.topological_sort("abcbca".chars()).unwrap();
+        assert_eq!(sorted, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_topological_sort_with_cycle() {
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('b', 'c'), ('c', 'a'), ('c', 'd')],
+        };
+        assert!(matches!(
+            graph.topological_sort("abcd".chars()),
+            Err(Error::GraphHasCycle)
+        ));
+    }
+
+    fn check_eulerian_path(graph: &UnweightedGraph, path: &[char]) {
+        let used_edges: Vec<(char, char)> = path
+            .iter()
+            .tuple_windows()
+            .map(|(&from, &to)| (from, to))
+            .sorted()
+            .collect();
+        let expected_edges: Vec<(char, char)> =
+            graph.edges.iter().cloned().sorted().collect();
+        assert_eq!(used_edges, expected_edges);
+    }
+
+    #[test]
+    fn test_eulerian_circuit() {
+        // Two triangles sharing the node 'c'.
+        let graph = UnweightedGraph {
+            edges: vec![
+                ('a', 'b'),
+                ('b', 'c'),
+                ('c', 'a'),
+                ('c', 'd'),
+                ('d', 'e'),
+                ('e', 'c'),
+            ],
+        };
+        let path = graph.eulerian_path("abcde".chars()).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), path.last());
+        check_eulerian_path(&graph, &path);
+    }
+
+    #[test]
+    fn test_eulerian_path() {
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('b', 'c'), ('c', 'b'), ('b', 'd')],
+        };
+        let path = graph.eulerian_path("abcd".chars()).unwrap();
+        assert_eq!(path.first(), Some(&'a'));
+        assert_eq!(path.last(), Some(&'d'));
+        check_eulerian_path(&graph, &path);
+    }
+
+    #[test]
+    fn test_no_eulerian_path() {
+        // Two edges leaving 'a', with no way to return.
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('a', 'c'), ('b', 'c')],
+        };
+        assert_eq!(graph.eulerian_path("abc".chars()), None);
+
+        // Balanced degrees, but in two disconnected cycles.
+        let graph = UnweightedGraph {
+            edges: vec![('a', 'b'), ('b', 'a'), ('c', 'd'), ('d', 'c')],
+        };
+        assert_eq!(graph.eulerian_path("abcd".chars()), None);
+    }
+
+    struct WeightedGraph {
+        edges: Vec<(char, char, u64)>,
+    }
+
+    impl EdgeWeightedGraph<char> for WeightedGraph {
+        fn connections_from<'a>(
+            &'a self,
+            node: &'a char,
+        ) -> impl Iterator<Item = (char, u64)> + 'a {
+            self.edges
+                .iter()
+                .filter(move |(from, _, _)| from == node)
+                .map(|&(_, to, weight)| (to, weight))
+        }
+    }
+
+    fn example_weighted_graph() -> WeightedGraph {
+        WeightedGraph {
+            edges: vec![
+                ('a', 'b', 7),
+                ('a', 'c', 9),
+                ('a', 'f', 14),
+                ('b', 'c', 10),
+                ('b', 'd', 15),
+                ('c', 'd', 11),
+                ('c', 'f', 2),
+                ('d', 'e', 6),
+                ('e', 'f', 9),
+                ('f', 'e', 9),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_a_star_to() {
+        let maze = Maze {
+            x_size: 5,
+            y_size: 3,
+            walls: vec![(2, 0), (1, 1), (2, 1), (4, 1)],
+        };
+        let graph = WeightedMaze(&maze);
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i64, i64)| {
+            Some(x.abs_diff(goal.0) + y.abs_diff(goal.1))
+        };
+        assert_eq!(
+            graph.a_star_to([(0, 0)], manhattan, |&pos| pos == goal),
+            Some(((4, 0), 8))
+        );
+        assert_eq!(
+            graph.a_star_to([(0, 0)], |_| Some(0), |&pos| pos.0 == 3),
+            Some(((3, 2), 5))
+        );
+        assert_eq!(
+            graph.a_star_to([(0, 0)], |_| Some(0), |&pos| pos == (2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_to() {
+        let graph = example_weighted_graph();
+        let path = graph.shortest_path_to(['a'], |&node| node == 'e');
+        assert_eq!(path, Some(vec![('c', 9), ('f', 2), ('e', 9)]));
+
+        let path = graph.shortest_path_to(['a'], |&node| node == 'a');
+        assert_eq!(path, Some(vec![]));
+
+        let path = graph.shortest_path_to(['e'], |&node| node == 'a');
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_all_pairs_shortest_paths() {
+        let graph = example_weighted_graph();
+        let nodes: Vec<char> = "abcdef".chars().collect();
+        let all_pairs = graph.all_pairs_shortest_paths(&nodes);
+
+        for &initial in &nodes {
+            let from_dijkstra: HashMap<(char, char), u64> = graph
+                .iter_dijkstra([initial])
+                .filter(|search_item| search_item.item != initial)
+                .map(|search_item| {
+                    ((initial, search_item.item), search_item.total_dist)
+                })
+                .collect();
+            let from_floyd_warshall: HashMap<(char, char), u64> = all_pairs
+                .iter()
+                .filter(|((from, _), _)| *from == initial)
+                .map(|(&key, &dist)| (key, dist))
+                .collect();
+            assert_eq!(from_dijkstra, from_floyd_warshall);
+        }
+
+        assert_eq!(all_pairs[&('a', 'e')], 20);
+        assert!(!all_pairs.contains_key(&('e', 'a')));
+        assert!(!all_pairs.contains_key(&('a', 'a')));
+
+        let contracted = graph.contract_to(nodes.iter().copied(), |_| true);
+        assert_eq!(all_pairs, contracted);
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let graph = example_weighted_graph();
+        let tree = graph.minimum_spanning_tree("abcdef".chars());
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.iter().map(|(_, _, weight)| weight).sum::<u64>(), 33);
+
+        let mut sets = UnionFind::new(6);
+        let index = |c: char| (c as usize) - ('a' as usize);
+        for &(a, b, _) in &tree {
+            assert!(sets.union(index(a), index(b)));
+        }
+        assert_eq!(sets.set_size(0), 6);
+    }
+
+    #[test]
+    fn test_longest_path_dag() {
+        let graph = example_weighted_graph();
+        // Removing the edges out of 'f' breaks the 'e' <-> 'f' cycle.
+        let graph = WeightedGraph {
+            edges: graph
+                .edges
+                .into_iter()
+                .filter(|(from, _, _)| *from != 'f')
+                .collect(),
+        };
+        assert_eq!(graph.longest_path_dag('a', 'f').unwrap(), Some(43));
+        assert_eq!(graph.longest_path_dag('a', 'd').unwrap(), Some(28));
+        assert_eq!(graph.longest_path_dag('d', 'a').unwrap(), None);
+    }
+
+    #[test]
+    fn test_longest_path_dag_with_cycle() {
+        let graph = example_weighted_graph();
+        assert!(matches!(
+            graph.longest_path_dag('a', 'f'),
+            Err(Error::GraphHasCycle)
+        ));
+    }
+
+    // Each state is the current node and the total value collected
+    // along the path to reach it.
+    struct ValueCollection {
+        edges: Vec<(char, char, i64)>,
+    }
+
+    impl DynamicGraph<(char, i64)> for ValueCollection {
+        fn connections_from(
+            &self,
+            node: &(char, i64),
+        ) -> Vec<((char, i64), u64)> {
+            let (node, value) = *node;
+            self.edges
+                .iter()
+                .filter(|(from, _, _)| *from == node)
+                .map(|&(_, to, new_value)| ((to, value + new_value), 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_contract_to() {
+        // A chain a-b-c-d-e, with edges in both directions.
+        let graph = WeightedGraph {
+            edges: [('a', 'b', 1), ('b', 'c', 2), ('c', 'd', 3), ('d', 'e', 4)]
+                .into_iter()
+                .flat_map(|(a, b, weight)| [(a, b, weight), (b, a, weight)])
+                .collect(),
+        };
+        let keep = |node: &char| "ace".contains(*node);
+
+        let contracted = graph.contract_to(['a'], keep);
+        let expected: HashMap<(char, char), u64> = [
+            (('a', 'c'), 3),
+            (('a', 'e'), 10),
+            (('c', 'a'), 3),
+            (('c', 'e'), 7),
+            (('e', 'a'), 10),
+            (('e', 'c'), 7),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(contracted, expected);
+
+        // Starting from a node that isn't kept includes paths from it,
+        // but not to it.
+        let contracted = graph.contract_to(['b'], keep);
+        assert_eq!(contracted.len(), 9);
+        assert_eq!(contracted[&('b', 'a')], 1);
+        assert_eq!(contracted[&('b', 'e')], 9);
+        assert!(!contracted.contains_key(&('a', 'b')));
+    }
+
+    #[test]
+    fn test_shortest_path_length() {
+        let weighted = example_weighted_graph();
+        let graph = AsDynamicGraph(&weighted);
+
+        "abcdef".chars().for_each(|target| {
+            let path_cost: u64 = graph
+                .shortest_path('a', target)
+                .unwrap()
+                .into_iter()
+                .map(|(_, cost)| cost)
+                .sum();
+            assert_eq!(
+                graph.shortest_path_length('a', target),
+                Some(path_cost)
+            );
+        });
+        assert_eq!(graph.shortest_path_length('a', 'e'), Some(20));
+        assert_eq!(graph.shortest_path_length('e', 'a'), None);
+    }
+
+    #[test]
+    fn test_ida_star() {
+        let weighted = example_weighted_graph();
+        let graph = AsDynamicGraph(&weighted);
+
+        "abcdef".chars().for_each(|target| {
+            let (path, cost) =
+                graph.ida_star('a', |_| 0, |&node| node == target).unwrap();
+            assert_eq!(Some(cost), graph.shortest_path_length('a', target));
+            assert_eq!(path.first(), Some(&'a'));
+            assert_eq!(path.last(), Some(&target));
+        });
+
+        let (path, cost) =
+            graph.ida_star('a', |_| 0, |&node| node == 'e').unwrap();
+        assert_eq!(path, vec!['a', 'c', 'f', 'e']);
+        assert_eq!(cost, 20);
+
+        assert_eq!(graph.ida_star('e', |_| 0, |&node| node == 'a'), None);
+    }
+
+    #[test]
+    fn test_ida_star_with_heuristic() {
+        let maze = Maze {
+            x_size: 5,
+            y_size: 3,
+            walls: vec![(2, 0), (1, 1), (2, 1), (4, 1)],
+        };
+        let weighted = WeightedMaze(&maze);
+        let graph = AsDynamicGraph(&weighted);
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i64, i64)| -> u64 {
+            ((goal.0 - x).abs() + (goal.1 - y).abs()) as u64
+        };
+
+        let (path, cost) = graph
+            .ida_star((0, 0), manhattan, |&pos| pos == goal)
+            .unwrap();
+        assert_eq!(Some(cost), graph.shortest_path_length((0, 0), goal));
+        assert_eq!(path.len() as u64, cost + 1);
+    }
+
+    #[test]
+    fn test_dijkstra_fn() {
+        // Example from 2021 day 15, where the cost of entering each
+        // cell is given by its digit.
+        let risk = crate::GridMap::parse_digits([
+            "1163751742",
+            "1381373672",
+            "2136511328",
+            "3694931569",
+            "7463417111",
+            "1319128137",
+            "1359912421",
+            "3125421639",
+            "1293138521",
+            "2311944581",
+        ])
+        .unwrap();
+        let start = risk.top_left();
+        let goal = risk.bottom_right();
+
+        let neighbors = |&pos: &crate::GridPos| -> Vec<(crate::GridPos, u64)> {
+            risk.adjacent_points(pos, crate::Adjacency::Rook)
+                .map(|adj| (adj, risk[adj] as u64))
+                .collect()
+        };
+        assert_eq!(dijkstra_fn(start, neighbors, |&pos| pos == goal), Some(40));
+        assert_eq!(dijkstra_fn(start, neighbors, |&pos| pos == start), Some(0));
+    }
+
+    #[test]
+    fn test_dijkstra_fn_unreachable() {
+        let neighbors = |&n: &u64| -> Vec<(u64, u64)> {
+            if n < 10 {
+                vec![(n + 1, 1), (n + 2, 3)]
+            } else {
+                vec![]
+            }
+        };
+        assert_eq!(dijkstra_fn(0, neighbors, |&n| n == 6), Some(6));
+        assert_eq!(dijkstra_fn(0, neighbors, |&n| n == 20), None);
+    }
+
+    #[test]
+    fn test_dijkstra_fn_self_loop() {
+        let mut num_expanded = 0;
+        let neighbors = |&n: &u64| -> Vec<(u64, u64)> {
+            num_expanded += 1;
+            if n < 3 {
+                vec![(n, 0), (n + 1, 2)]
+            } else {
+                vec![(n, 0)]
+            }
+        };
+        assert_eq!(dijkstra_fn(0, neighbors, |&n| n == 10), None);
+        assert_eq!(num_expanded, 4);
+
+        let neighbors = |&n: &u64| vec![(n, 0), (n + 1, 2)];
+        assert_eq!(dijkstra_fn(0, neighbors, |&n| n == 3), Some(6));
+    }
+
+    #[test]
+    fn test_beam_search() {
+        let graph = ValueCollection {
+            edges: vec![
+                ('a', 'b', 1),
+                ('a', 'c', 5),
+                ('b', 'd', 10),
+                ('c', 'd', 1),
+                ('d', 'e', 0),
+            ],
+        };
+        let score = |state: &(char, i64)| state.1;
+        let is_goal = |state: &(char, i64)| state.0 == 'e';
+
+        let path = graph.beam_search(('a', 0), 2, score, is_goal).unwrap();
+        assert_eq!(path, vec![('a', 0), ('b', 1), ('d', 11), ('e', 11)]);
+
+        // A narrow beam prunes the optimal path, since it passes
+        // through a low-scoring state.
+        let path = graph.beam_search(('a', 0), 1, score, is_goal).unwrap();
+        assert_eq!(path, vec![('a', 0), ('c', 5), ('d', 6), ('e', 6)]);
+
+        let unreachable = |state: &(char, i64)| state.0 == 'z';
+        assert_eq!(graph.beam_search(('a', 0), 2, score, unreachable), None);
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let graph = SignedGraph {
+            edges: vec![
+                ('a', 'b', 4),
+                ('a', 'c', 5),
+                ('b', 'd', 3),
+                ('c', 'b', -3),
+                ('d', 'e', -1),
+                ('f', 'a', 1),
+            ],
+        };
+        let distances = graph.bellman_ford('a', 6).unwrap();
+        let expected: HashMap<char, i64> =
+            [('a', 0), ('b', 2), ('c', 5), ('d', 5), ('e', 4)]
+                .into_iter()
+                .collect();
+        assert_eq!(distances, expected);
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        let graph = SignedGraph {
+            edges: vec![
+                ('a', 'b', 1),
+                ('b', 'c', 2),
+                ('c', 'b', -3),
+                ('c', 'd', 1),
+            ],
+        };
+        assert!(matches!(
+            graph.bellman_ford('a', 4),
+            Err(Error::NegativeCycle)
+        ));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::direction::Direction;
 use crate::extensions::CharIterLocExt;
-use crate::geometry::Vector;
+use crate::geometry::{BoundingBox, Vector};
+use crate::graph::EdgeWeightedGraph;
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
 use std::iter::FromIterator;
 use std::ops::{Index, IndexMut};
 
@@ -19,15 +23,38 @@ pub struct GridPos {
     index: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum GridMapError {
+    #[error("Rows of the grid have different lengths")]
     InconsistentLineSize,
+    #[error("Grid position has no value")]
     MissingValue,
+    #[error("Grid position has multiple values")]
     DuplicateValue,
+    #[error("Linear index is outside of the grid")]
     InvalidLinearIndex,
+    #[error("(x,y) index is outside of the grid")]
     InvalidXYIndex,
+    #[error("Corners of the rectangle are out of order")]
+    CornersOutOfOrder,
+    #[error("Character '{0}' is not a decimal digit")]
+    InvalidDigit(char),
 }
 
+/// View of a `GridMap` as a graph, where each cell is connected to
+/// its in-bounds neighbors.  Constructed with
+/// `GridMap::as_weighted_graph`.
+pub struct GridMapGraph<'map, T, F> {
+    map: &'map GridMap<T>,
+    adjacency: Adjacency,
+    cost: F,
+}
+
+/// A collection of cells within a `GridMap`, along with their
+/// positions.
+pub type CellList<'map, T> = Vec<(GridPos, &'map T)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Adjacency {
     Rook,
     Queen,
@@ -287,6 +314,28 @@ where
     }
 }
 
+impl GridMap<u8> {
+    /// Parse a grid in which each character is a single decimal
+    /// digit, storing the numeric value of each digit.
+    pub fn parse_digits<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, GridMapError> {
+        let rows = lines
+            .into_iter()
+            .map(|line| {
+                line.chars()
+                    .map(|c| {
+                        c.to_digit(10)
+                            .map(|digit| digit as u8)
+                            .ok_or(GridMapError::InvalidDigit(c))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_rows(rows)
+    }
+}
+
 impl<T> Display for GridMap<T>
 where
     T: Display,
@@ -319,6 +368,23 @@ impl<T> GridMap<T> {
         }
     }
 
+    /// Construct a grid from a list of rows, ordered from top to
+    /// bottom.  All rows must have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridMapError> {
+        let x_size = rows.first().map_or(0, |row| row.len());
+        let y_size = rows.len();
+        if rows.iter().any(|row| row.len() != x_size) {
+            return Err(GridMapError::InconsistentLineSize);
+        }
+
+        let values = rows.into_iter().flatten().collect();
+        Ok(Self {
+            x_size,
+            y_size,
+            values,
+        })
+    }
+
     pub fn is_valid(&self, index: impl IntoGridPos) -> bool {
         index.into_grid_pos(self).is_ok()
     }
@@ -375,6 +441,35 @@ impl<T> GridMap<T> {
             .map(|pos| FromGridPos::from_grid_pos(pos, self))
     }
 
+    /// In-bounds neighbors of the position, along with their values.
+    pub fn adjacent(
+        &self,
+        pos: impl IntoGridPos,
+        adj: Adjacency,
+    ) -> impl Iterator<Item = (GridPos, &T)> + '_ {
+        self.adjacent_points_internal(pos, adj)
+            .unwrap()
+            .flatten()
+            .map(|pos| FromGridPos::from_grid_pos(pos, self))
+    }
+
+    /// Iterate over the neighbors of `pos`, treating the grid as a
+    /// torus.  Neighbors that would lie off one edge of the grid wrap
+    /// around to the opposite edge.
+    pub fn adjacent_wrapping(
+        &self,
+        pos: impl IntoGridPos,
+        adj: Adjacency,
+    ) -> impl Iterator<Item = (GridPos, &T)> + '_ {
+        let (x0, y0) = pos.into_grid_pos(self).unwrap().as_xy(self);
+        adj.offsets().map(move |(dx, dy)| {
+            let x = (x0 + dx).rem_euclid(self.x_size as i64);
+            let y = (y0 + dy).rem_euclid(self.y_size as i64);
+            let pos = (x, y).into_grid_pos(self).unwrap();
+            (pos, &self[pos])
+        })
+    }
+
     pub fn adjacent_values_default(
         &self,
         pos: impl IntoGridPos,
@@ -391,6 +486,215 @@ impl<T> GridMap<T> {
             })
     }
 
+    /// Generate a new grid, where each value is computed from the
+    /// current value of the cell and the values of its neighbors.
+    /// The neighbors are passed in the order given by
+    /// `Adjacency::offsets`, using `default` for any neighbor that
+    /// lies outside the grid.
+    pub fn map_neighborhood<U>(
+        &self,
+        adj: Adjacency,
+        default: T,
+        f: impl Fn(&T, &[T]) -> U,
+    ) -> GridMap<U>
+    where
+        T: Clone,
+    {
+        let mut neighbors = Vec::new();
+        let values = self
+            .iter_pos()
+            .map(|(pos, value)| {
+                neighbors.clear();
+                neighbors.extend(self.adjacent_values_default(
+                    pos,
+                    adj,
+                    default.clone(),
+                ));
+                f(value, &neighbors)
+            })
+            .collect();
+        GridMap {
+            x_size: self.x_size,
+            y_size: self.y_size,
+            values,
+        }
+    }
+
+    /// Update every cell simultaneously, as in `map_neighborhood`.
+    /// The new values are computed from the current values alone,
+    /// written into a back buffer, and then swapped with the current
+    /// values once all cells are computed.
+    pub fn step_in_place(
+        &mut self,
+        adj: Adjacency,
+        default: T,
+        f: impl Fn(&T, &[T]) -> T,
+    ) where
+        T: Clone,
+    {
+        let mut neighbors = Vec::new();
+        let mut next = Vec::with_capacity(self.values.len());
+        next.extend(self.iter_pos().map(|(pos, value)| {
+            neighbors.clear();
+            neighbors.extend(self.adjacent_values_default(
+                pos,
+                adj,
+                default.clone(),
+            ));
+            f(value, &neighbors)
+        }));
+        std::mem::swap(&mut self.values, &mut next);
+    }
+
+    /// All positions reachable from `start`, including `start`
+    /// itself.  A step from one cell to an adjacent cell may be taken
+    /// if `predicate(current_value, neighbor_value)` returns true.
+    pub fn flood_fill(
+        &self,
+        start: GridPos,
+        adjacency: Adjacency,
+        predicate: impl Fn(&T, &T) -> bool,
+    ) -> HashSet<GridPos> {
+        let mut seen: HashSet<GridPos> = std::iter::once(start).collect();
+        let mut to_visit = vec![start];
+
+        while let Some(pos) = to_visit.pop() {
+            self.adjacent_points(pos, adjacency)
+                .filter(|&neighbor| predicate(&self[pos], &self[neighbor]))
+                .for_each(|neighbor| {
+                    if seen.insert(neighbor) {
+                        to_visit.push(neighbor);
+                    }
+                });
+        }
+
+        seen
+    }
+
+    /// Partition the grid into connected regions, where adjacent
+    /// cells belong to the same region if `same_group` returns true
+    /// for their values.  `same_group` should be symmetric.  Each
+    /// region is returned in row-major order, and regions are ordered
+    /// by their first cell in row-major order.
+    pub fn connected_component_groups(
+        &self,
+        adjacency: Adjacency,
+        same_group: impl Fn(&T, &T) -> bool,
+    ) -> Vec<Vec<GridPos>> {
+        let mut assigned: HashSet<GridPos> = HashSet::new();
+        self.iter()
+            .filter_map(|pos: GridPos| {
+                if assigned.contains(&pos) {
+                    return None;
+                }
+                let group: Vec<GridPos> = self
+                    .flood_fill(pos, adjacency, &same_group)
+                    .into_iter()
+                    .sorted_by_key(|pos| pos.as_flat())
+                    .collect();
+                assigned.extend(group.iter().cloned());
+                Some(group)
+            })
+            .collect()
+    }
+
+    /// Label each cell with the index of its connected region, as
+    /// returned by `connected_component_groups`.
+    pub fn connected_components(
+        &self,
+        adjacency: Adjacency,
+        same_group: impl Fn(&T, &T) -> bool,
+    ) -> GridMap<usize> {
+        let mut labels = GridMap::new_uniform(self.x_size, self.y_size, 0);
+        self.connected_component_groups(adjacency, same_group)
+            .into_iter()
+            .enumerate()
+            .for_each(|(label, group)| {
+                group.into_iter().for_each(|pos| {
+                    labels[pos] = label;
+                })
+            });
+        labels
+    }
+
+    /// Treat the grid as a graph for use in path-finding.  The
+    /// weight of an edge is given by `cost(from, to)`.
+    pub fn as_weighted_graph<F>(
+        &self,
+        adjacency: Adjacency,
+        cost: F,
+    ) -> GridMapGraph<'_, T, F>
+    where
+        F: Fn(GridPos, GridPos) -> u64,
+    {
+        GridMapGraph {
+            map: self,
+            adjacency,
+            cost,
+        }
+    }
+
+    /// Find the minimum cost to travel from `start` to `goal`, where
+    /// each move must continue in a straight line for at least
+    /// `min_run` steps before turning, and may go at most `max_run`
+    /// steps before it must turn.  Moves may not reverse direction,
+    /// and the goal may only be reached at the end of a run at least
+    /// `min_run` steps long.  Entering a cell costs `cost(value)`.
+    /// Returns None if the goal cannot be reached.
+    pub fn directional_search(
+        &self,
+        start: impl IntoGridPos,
+        goal: impl IntoGridPos,
+        min_run: usize,
+        max_run: usize,
+        cost: impl Fn(&T) -> u64,
+    ) -> Option<u64> {
+        let start = self.grid_pos(start)?;
+        let goal = self.grid_pos(goal)?;
+        let graph = DirectionalGraph {
+            map: self,
+            min_run,
+            max_run,
+            cost,
+        };
+        let initial = Direction::iter_all().map(move |dir| (start, dir, 0));
+
+        let min_cost = graph
+            .iter_dijkstra(initial)
+            .find(|search_item| {
+                let (pos, _, run) = search_item.item;
+                pos == goal && (pos == start || run >= min_run)
+            })
+            .map(|search_item| search_item.total_dist);
+        min_cost
+    }
+
+    /// Breadth-first expansion from `start`, returning the positions
+    /// first reached at each step.  The first layer contains only
+    /// `start`.  Cells for which `passable` returns false are never
+    /// entered.
+    pub fn flood_fill_layered(
+        &self,
+        start: GridPos,
+        adj: Adjacency,
+        passable: impl Fn(&T) -> bool,
+    ) -> Vec<HashSet<GridPos>> {
+        let mut seen: HashSet<GridPos> = std::iter::once(start).collect();
+        std::iter::successors(
+            Some(std::iter::once(start).collect()),
+            |prev: &HashSet<GridPos>| {
+                let layer: HashSet<GridPos> = prev
+                    .iter()
+                    .flat_map(|&pos| self.adjacent_points(pos, adj))
+                    .filter(|&pos| passable(&self[pos]))
+                    .filter(|&pos| seen.insert(pos))
+                    .collect();
+                (!layer.is_empty()).then_some(layer)
+            },
+        )
+        .collect()
+    }
+
     pub fn iter<'map, Item>(&'map self) -> impl Iterator<Item = Item> + '_
     where
         Item: FromGridPos<'map, T>,
@@ -412,6 +716,69 @@ impl<T> GridMap<T> {
         self.iter()
     }
 
+    /// Iterate over each row, from top to bottom.  Each row is
+    /// iterated from left to right.
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let width = self.x_size;
+        (0..self.y_size)
+            .map(move |y| self.values[y * width..(y + 1) * width].iter())
+    }
+
+    /// Iterate over each column, from left to right.  Each column is
+    /// iterated from top to bottom.
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let width = self.x_size;
+        (0..width).map(move |x| self.values[x..].iter().step_by(width))
+    }
+
+    /// Group the positions of the grid by the value at each position.
+    /// Within each group, positions are in the same order as
+    /// `iter_pos`.
+    pub fn positions_by_value(&self) -> HashMap<&T, Vec<GridPos>>
+    where
+        T: Eq + Hash,
+    {
+        self.iter_pos()
+            .map(|(pos, value)| (value, pos))
+            .into_group_map()
+    }
+
+    /// Split the cells into a checkerboard pattern, returning the
+    /// cells where `x + y` is even, followed by the cells where `x +
+    /// y` is odd.
+    pub fn iter_by_parity(&self) -> (CellList<'_, T>, CellList<'_, T>) {
+        self.iter_pos().partition(|(pos, _)| {
+            let (x, y) = pos.as_xy(self);
+            (x + y) % 2 == 0
+        })
+    }
+
+    /// List the cells whose values differ between `self` and `other`,
+    /// along with the value in each grid.  Panics if the two grids
+    /// have different shapes.
+    pub fn diff<'a>(
+        &'a self,
+        other: &'a GridMap<T>,
+    ) -> Vec<(GridPos, &'a T, &'a T)>
+    where
+        T: PartialEq,
+    {
+        assert_eq!(
+            self.shape(),
+            other.shape(),
+            "Cannot diff grids of different shapes"
+        );
+        self.iter_pos()
+            .zip(other.iter_item())
+            .filter(|((_, a), b)| a != b)
+            .map(|((pos, a), b)| (pos, a, b))
+            .collect()
+    }
+
     pub fn iter_pos_mut(&mut self) -> impl Iterator<Item = (GridPos, &mut T)> {
         self.values
             .iter_mut()
@@ -462,6 +829,18 @@ impl<T> GridMap<T> {
         })
     }
 
+    /// Same as `iter_ray`, but stops after taking `max_steps` steps
+    /// from the start, or after leaving the grid, whichever happens
+    /// first.
+    pub fn iter_ray_bounded(
+        &self,
+        start: GridPos,
+        step: impl Into<(i64, i64)>,
+        max_steps: usize,
+    ) -> impl Iterator<Item = (GridPos, &T)> + '_ {
+        self.iter_ray(start, step).take(max_steps + 1)
+    }
+
     pub fn iter_ray_wrapping(
         &self,
         start: GridPos,
@@ -479,6 +858,52 @@ impl<T> GridMap<T> {
         })
     }
 
+    /// Determine which cells can be seen from outside the grid,
+    /// looking inward along a row or column.  The closure
+    /// `blocks(cell, other)` returns true if `other` hides `cell` when
+    /// `other` lies between `cell` and the edge.
+    pub fn visible_from_edges(
+        &self,
+        blocks: impl Fn(&T, &T) -> bool,
+    ) -> GridMap<bool> {
+        let values = self
+            .iter_pos()
+            .map(|(pos, value)| {
+                Adjacency::Rook.offsets().any(|offset| {
+                    self.iter_ray(pos, offset)
+                        .skip(1)
+                        .all(|(_, other)| !blocks(value, other))
+                })
+            })
+            .collect();
+        GridMap {
+            x_size: self.x_size,
+            y_size: self.y_size,
+            values,
+        }
+    }
+
+    /// The product, over each of the four rook directions, of the
+    /// number of cells visible from `pos`.  A blocking cell, as
+    /// determined by `blocks(&self[pos], other)`, is itself visible
+    /// but ends the line of sight.
+    pub fn scenic_score(
+        &self,
+        pos: GridPos,
+        blocks: impl Fn(&T, &T) -> bool,
+    ) -> usize {
+        let value = &self[pos];
+        Adjacency::Rook
+            .offsets()
+            .map(|offset| {
+                self.iter_ray(pos, offset)
+                    .skip(1)
+                    .take_while_inclusive(|(_, other)| !blocks(value, other))
+                    .count()
+            })
+            .product()
+    }
+
     pub fn iter_rect(
         &self,
         corner_a: GridPos,
@@ -492,6 +917,110 @@ impl<T> GridMap<T> {
             .map(|gridpos| (gridpos, &self[gridpos]))
     }
 
+    // Generate a new grid of the specified size, where the value at
+    // each (x,y) is copied from `source_xy(x,y)` in the current grid.
+    fn rearranged(
+        &self,
+        x_size: usize,
+        y_size: usize,
+        source_xy: impl Fn(usize, usize) -> (usize, usize),
+    ) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let values = (0..y_size)
+            .flat_map(|y| (0..x_size).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (src_x, src_y) = source_xy(x, y);
+                self.values[src_y * self.x_size + src_x].clone()
+            })
+            .collect();
+        GridMap {
+            x_size,
+            y_size,
+            values,
+        }
+    }
+
+    /// Rotate the grid by 90 degrees clockwise, as displayed.
+    pub fn rotate_cw(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(height, width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotate the grid by 90 degrees counter-clockwise, as displayed.
+    pub fn rotate_ccw(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(height, width, |x, y| (width - 1 - y, x))
+    }
+
+    pub fn rotate_180(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(width, height, |x, y| (width - 1 - x, height - 1 - y))
+    }
+
+    /// Swap the rows and columns of the grid.
+    pub fn transpose(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(height, width, |x, y| (y, x))
+    }
+
+    /// Mirror the grid left-to-right.
+    pub fn flip_horizontal(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(width, height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirror the grid top-to-bottom.
+    pub fn flip_vertical(&self) -> GridMap<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = self.shape();
+        self.rearranged(width, height, |x, y| (x, height - 1 - y))
+    }
+
+    /// Copy the rectangular region between the two corners,
+    /// inclusive, into a new grid.
+    pub fn crop(
+        &self,
+        top_left: GridPos,
+        bottom_right: GridPos,
+    ) -> Result<GridMap<T>, GridMapError>
+    where
+        T: Clone,
+    {
+        let (x_min, y_min) = top_left.as_xy(self);
+        let (x_max, y_max) = bottom_right.as_xy(self);
+        if x_min > x_max || y_min > y_max {
+            return Err(GridMapError::CornersOutOfOrder);
+        }
+
+        Ok(GridMap {
+            x_size: (x_max - x_min + 1) as usize,
+            y_size: (y_max - y_min + 1) as usize,
+            values: self
+                .iter_rect(top_left, bottom_right)
+                .map(|(_, value)| value.clone())
+                .collect(),
+        })
+    }
+
     pub fn map<'map, Arg, F, U>(&'map self, mut func: F) -> GridMap<U>
     where
         Arg: FromGridPos<'map, T>,
@@ -507,6 +1036,62 @@ impl<T> GridMap<T> {
     }
 }
 
+impl<T, F> EdgeWeightedGraph<GridPos> for GridMapGraph<'_, T, F>
+where
+    F: Fn(GridPos, GridPos) -> u64,
+{
+    fn connections_from<'a>(
+        &'a self,
+        node: &'a GridPos,
+    ) -> impl Iterator<Item = (GridPos, u64)> + 'a {
+        let node = *node;
+        self.map
+            .adjacent_points(node, self.adjacency)
+            .map(move |adj| (adj, (self.cost)(node, adj)))
+    }
+}
+
+/// View of a `GridMap` as a graph whose nodes also track the
+/// direction of travel and the number of steps taken in that
+/// direction.  Used by `GridMap::directional_search`.
+struct DirectionalGraph<'map, T, F> {
+    map: &'map GridMap<T>,
+    min_run: usize,
+    max_run: usize,
+    cost: F,
+}
+
+impl<T, F> EdgeWeightedGraph<(GridPos, Direction, usize)>
+    for DirectionalGraph<'_, T, F>
+where
+    F: Fn(&T) -> u64,
+{
+    fn connections_from<'a>(
+        &'a self,
+        node: &'a (GridPos, Direction, usize),
+    ) -> impl Iterator<Item = ((GridPos, Direction, usize), u64)> + 'a {
+        let (pos, dir, run) = *node;
+        let can_turn = run == 0 || run >= self.min_run;
+        let can_continue = run < self.max_run;
+
+        [
+            (dir, can_continue),
+            (dir.turn_left(), can_turn),
+            (dir.turn_right(), can_turn),
+        ]
+        .into_iter()
+        .filter(|(_, allowed)| *allowed)
+        .filter_map(move |(new_dir, _)| {
+            let new_pos = self
+                .map
+                .grid_pos(pos.as_vec(self.map) + new_dir.to_vector())?;
+            let new_run = if new_dir == dir { run + 1 } else { 1 };
+            let cost = (self.cost)(&self.map[new_pos]);
+            Some(((new_pos, new_dir, new_run), cost))
+        })
+    }
+}
+
 pub trait CollectResizedGridMap<T> {
     fn collect_resized_grid_map(self, default: T) -> GridMap<T>;
 }
@@ -518,20 +1103,10 @@ where
 {
     fn collect_resized_grid_map(self, default: T) -> GridMap<T> {
         let tuples: Vec<(Vector<2, i64>, T)> = self.collect();
-        let (xmin, xmax) = tuples
-            .iter()
-            .map(|(p, _)| p.x())
-            .minmax()
-            .into_option()
-            .unwrap();
-        let (ymin, ymax) = tuples
-            .iter()
-            .map(|(p, _)| p.y())
-            .minmax()
-            .into_option()
-            .unwrap();
-        let x_size = (xmax - xmin) + 1;
-        let y_size = (ymax - ymin) + 1;
+        let bbox =
+            BoundingBox::from_points(tuples.iter().map(|(p, _)| *p)).unwrap();
+        let (xmin, ymin) = bbox.min.into();
+        let (x_size, y_size) = bbox.shape().into();
 
         let values = tuples
             .into_iter()
@@ -617,3 +1192,484 @@ impl<T, Arg: IntoGridPos> IndexMut<Arg> for GridMap<T> {
         &mut self.values[grid_pos.index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_tree_heights() -> GridMap<u8> {
+        ["30373", "25512", "65332", "33549", "35390"]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_visible_from_edges() {
+        let map = example_tree_heights();
+        let visible = map.visible_from_edges(|height, other| other >= height);
+        assert_eq!(visible.iter_item().filter(|&&b| b).count(), 21);
+        assert!(visible[(1, 1)]);
+        assert!(!visible[(2, 2)]);
+        assert!(!visible[(3, 1)]);
+    }
+
+    #[test]
+    fn test_scenic_score() {
+        let map = example_tree_heights();
+        let blocks = |height: &u8, other: &u8| other >= height;
+        let score = |x: i64, y: i64| {
+            map.scenic_score(map.grid_pos((x, y)).unwrap(), blocks)
+        };
+        assert_eq!(score(2, 1), 4);
+        assert_eq!(score(2, 3), 8);
+        assert_eq!(score(0, 0), 0);
+
+        let best = map
+            .iter_pos()
+            .map(|(pos, _)| map.scenic_score(pos, blocks))
+            .max();
+        assert_eq!(best, Some(8));
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let map = GridMap::parse_digits(["123", "456", "789"]).unwrap();
+        assert_eq!(map.shape(), (3, 3));
+        assert_eq!(map[(0, 0)], 1);
+        assert_eq!(map[(2, 0)], 3);
+        assert_eq!(map[(1, 2)], 8);
+        assert_eq!(map.iter_item().map(|&d| d as u32).sum::<u32>(), 45);
+
+        assert!(matches!(
+            GridMap::parse_digits(["123", "4a6"]),
+            Err(GridMapError::InvalidDigit('a'))
+        ));
+        assert!(matches!(
+            GridMap::parse_digits(["123", "45"]),
+            Err(GridMapError::InconsistentLineSize)
+        ));
+    }
+
+    #[test]
+    fn test_step_in_place_blinker() {
+        let life = |&alive: &bool, neighbors: &[bool]| {
+            let num_alive = neighbors.iter().filter(|&&b| b).count();
+            matches!((alive, num_alive), (true, 2 | 3) | (false, 3))
+        };
+        let parse = |rows: [&str; 5]| -> GridMap<bool> {
+            let rows = rows
+                .iter()
+                .map(|row| row.chars().map(|c| c == '#').collect())
+                .collect();
+            GridMap::from_rows(rows).unwrap()
+        };
+
+        let horizontal = parse([".....", ".....", ".###.", ".....", "....."]);
+        let vertical = parse([".....", "..#..", "..#..", "..#..", "....."]);
+
+        let mut map = horizontal.clone();
+        map.step_in_place(Adjacency::Queen, false, life);
+        assert_eq!(map, vertical);
+        map.step_in_place(Adjacency::Queen, false, life);
+        assert_eq!(map, horizontal);
+    }
+
+    #[test]
+    fn test_flood_fill_layered() {
+        let map: GridMap<char> = [".....", ".....", ".....", ".....", "....."]
+            .into_iter()
+            .collect();
+        let start = map.grid_pos((2, 2)).unwrap();
+
+        let layer_sizes: Vec<_> = map
+            .flood_fill_layered(start, Adjacency::Rook, |&c| c == '.')
+            .iter()
+            .map(|layer| layer.len())
+            .collect();
+        assert_eq!(layer_sizes, vec![1, 4, 8, 8, 4]);
+    }
+
+    #[test]
+    fn test_flood_fill_layered_walls() {
+        let map: GridMap<char> =
+            ["..#..", "..#..", "..#.."].into_iter().collect();
+        let start = map.top_left();
+
+        let layers =
+            map.flood_fill_layered(start, Adjacency::Rook, |&c| c == '.');
+        let layer_sizes: Vec<_> =
+            layers.iter().map(|layer| layer.len()).collect();
+        assert_eq!(layer_sizes, vec![1, 2, 2, 1]);
+        assert!(layers.iter().flatten().all(|&pos| map[pos] == '.'));
+    }
+
+    #[test]
+    fn test_collect_resized_grid_map() {
+        let map = [(Vector::new([-1, 2]), 'a'), (Vector::new([1, 3]), 'b')]
+            .into_iter()
+            .collect_resized_grid_map('.');
+        assert_eq!(map.shape(), (3, 2));
+        assert_eq!(format!("{map}"), "a..\n..b\n");
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let map: GridMap<char> = ["#####", "#..##", "#.#.#", "##..#", "#####"]
+            .into_iter()
+            .collect();
+        let start = map.grid_pos((1, 1)).unwrap();
+
+        let rook = map.flood_fill(start, Adjacency::Rook, |_, &b| b == '.');
+        let expected: HashSet<_> = [(1, 1), (2, 1), (1, 2)]
+            .into_iter()
+            .map(|xy| map.grid_pos(xy).unwrap())
+            .collect();
+        assert_eq!(rook, expected);
+
+        let queen = map.flood_fill(start, Adjacency::Queen, |_, &b| b == '.');
+        let expected: HashSet<_> = map
+            .iter_pos()
+            .filter(|(_, &c)| c == '.')
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(queen, expected);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let map: GridMap<char> = ["aab", "abb", "cca"].into_iter().collect();
+
+        let labels = map.connected_components(Adjacency::Rook, |a, b| a == b);
+        let expected: GridMap<usize> = ["001", "011", "223"]
+            .into_iter()
+            .collect::<GridMap<char>>()
+            .map(|c: &char| c.to_digit(10).unwrap() as usize);
+        assert_eq!(labels, expected);
+
+        let groups =
+            map.connected_component_groups(Adjacency::Rook, |a, b| a == b);
+        let group_sizes: Vec<_> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(group_sizes, vec![3, 3, 2, 1]);
+        assert_eq!(groups[0][0], map.top_left());
+        assert_eq!(groups[3][0], map.bottom_right());
+    }
+
+    #[test]
+    fn test_as_weighted_graph() {
+        let map: GridMap<u64> = [
+            "1163751742",
+            "1381373672",
+            "2136511328",
+            "3694931569",
+            "7463417111",
+            "1319128137",
+            "1359912421",
+            "3125421639",
+            "1293138521",
+            "2311944581",
+        ]
+        .into_iter()
+        .collect::<GridMap<char>>()
+        .map(|c: &char| c.to_digit(10).unwrap() as u64);
+
+        let graph = map.as_weighted_graph(Adjacency::Rook, |_, to| map[to]);
+        let target = map.bottom_right();
+        let total_risk = graph
+            .iter_dijkstra([map.top_left()])
+            .find(|search_item| search_item.item == target)
+            .map(|search_item| search_item.total_dist);
+        assert_eq!(total_risk, Some(40));
+    }
+
+    #[test]
+    fn test_as_weighted_graph_edges() {
+        let map = GridMap::new_uniform(3, 2, ());
+        let graph = map.as_weighted_graph(Adjacency::Queen, |_, _| 1);
+        let corner_edges = graph.connections_from(&map.top_left()).count();
+        let center_edges = graph
+            .connections_from(&map.grid_pos((1, 0)).unwrap())
+            .count();
+        assert_eq!(corner_edges, 3);
+        assert_eq!(center_edges, 5);
+    }
+
+    #[test]
+    fn test_crop() {
+        let map: GridMap<char> = ["abcde", "fghij", "klmno", "pqrst", "uvwxy"]
+            .into_iter()
+            .collect();
+        let top_left = map.grid_pos((1, 2)).unwrap();
+        let bottom_right = map.grid_pos((2, 4)).unwrap();
+
+        let cropped = map.crop(top_left, bottom_right).unwrap();
+        assert_eq!(cropped.shape(), (2, 3));
+        assert_eq!(format!("{cropped}"), "lm\nqr\nvw\n");
+
+        assert!(matches!(
+            map.crop(bottom_right, top_left),
+            Err(GridMapError::CornersOutOfOrder)
+        ));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let map: GridMap<char> = ["abc", "def"].into_iter().collect();
+
+        let cw = map.rotate_cw();
+        assert_eq!(cw.shape(), (2, 3));
+        assert_eq!(format!("{cw}"), "da\neb\nfc\n");
+
+        let ccw = map.rotate_ccw();
+        assert_eq!(ccw.shape(), (2, 3));
+        assert_eq!(format!("{ccw}"), "cf\nbe\nad\n");
+
+        assert_eq!(format!("{}", map.rotate_180()), "fed\ncba\n");
+
+        assert_eq!(map.rotate_cw().rotate_ccw(), map);
+        assert_eq!(map.rotate_ccw().rotate_cw(), map);
+        assert_eq!(map.rotate_cw().rotate_cw(), map.rotate_180());
+        assert_eq!(map.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), map);
+    }
+
+    #[test]
+    fn test_flip() {
+        let map: GridMap<char> = ["abc", "def"].into_iter().collect();
+        assert_eq!(format!("{}", map.flip_horizontal()), "cba\nfed\n");
+        assert_eq!(format!("{}", map.flip_vertical()), "def\nabc\n");
+        assert_eq!(map.flip_horizontal().flip_vertical(), map.rotate_180());
+    }
+
+    #[test]
+    fn test_transpose() {
+        let map: GridMap<char> = ["abc", "def"].into_iter().collect();
+        let transposed = map.transpose();
+        assert_eq!(transposed.shape(), (2, 3));
+        assert_eq!(format!("{transposed}"), "ad\nbe\ncf\n");
+        assert_eq!(transposed.transpose(), map);
+    }
+
+    #[test]
+    fn test_adjacent() {
+        let map: GridMap<char> = ["abc", "def", "ghi"].into_iter().collect();
+
+        let values = |pos: (i64, i64), adj: Adjacency| -> String {
+            map.adjacent(pos, adj)
+                .inspect(|&(gridpos, value)| assert_eq!(&map[gridpos], value))
+                .map(|(_, value)| *value)
+                .sorted()
+                .collect()
+        };
+
+        assert_eq!(values((1, 1), Adjacency::Rook), "bdfh");
+        assert_eq!(values((1, 1), Adjacency::Queen), "abcdfghi");
+        assert_eq!(values((0, 0), Adjacency::Rook), "bd");
+        assert_eq!(values((0, 0), Adjacency::Queen), "bde");
+        assert_eq!(values((2, 1), Adjacency::Queen), "bcehi");
+    }
+
+    #[test]
+    fn test_adjacent_wrapping() {
+        let map: GridMap<char> = ["abcd", "efgh", "ijkl"].into_iter().collect();
+
+        let neighbors = |pos: (i64, i64), adj: Adjacency| -> Vec<(i64, i64)> {
+            map.adjacent_wrapping(pos, adj)
+                .map(|(gridpos, _)| gridpos.as_xy(&map))
+                .sorted()
+                .collect()
+        };
+
+        // The up and left neighbors of the top-left cell wrap to the
+        // bottom and right edges.
+        assert_eq!(
+            neighbors((0, 0), Adjacency::Rook),
+            vec![(0, 1), (0, 2), (1, 0), (3, 0)]
+        );
+        assert_eq!(
+            neighbors((3, 2), Adjacency::Rook),
+            vec![(0, 2), (2, 2), (3, 0), (3, 1)]
+        );
+
+        let values: String = map
+            .adjacent_wrapping((0, 0), Adjacency::Queen)
+            .map(|(_, c)| *c)
+            .sorted()
+            .collect();
+        assert_eq!(values, "bdefhijl");
+    }
+
+    #[test]
+    fn test_positions_by_value() {
+        let map: GridMap<char> =
+            ["..a.", "A...", "...a", ".A.."].into_iter().collect();
+        let groups = map.positions_by_value();
+
+        let xy = |c: char| -> Vec<(i64, i64)> {
+            groups[&c].iter().map(|pos| pos.as_xy(&map)).collect()
+        };
+        assert_eq!(xy('a'), vec![(2, 0), (3, 2)]);
+        assert_eq!(xy('A'), vec![(0, 1), (1, 3)]);
+        assert_eq!(groups[&'.'].len(), 12);
+        assert_eq!(groups.len(), 3);
+    }
+
+    #[test]
+    fn test_iter_by_parity() {
+        let map: GridMap<char> =
+            ["abc", "def", "ghi", "jkl"].into_iter().collect();
+        let (even, odd) = map.iter_by_parity();
+
+        let even_values: String = even.iter().map(|(_, c)| **c).collect();
+        let odd_values: String = odd.iter().map(|(_, c)| **c).collect();
+        assert_eq!(even_values, "acegik");
+        assert_eq!(odd_values, "bdfhjl");
+
+        let even_pos: HashSet<GridPos> =
+            even.iter().map(|(pos, _)| *pos).collect();
+        let odd_pos: HashSet<GridPos> =
+            odd.iter().map(|(pos, _)| *pos).collect();
+        assert!(even_pos.is_disjoint(&odd_pos));
+        assert_eq!(even_pos.len() + odd_pos.len(), 12);
+        assert!(map
+            .iter_pos()
+            .all(|(pos, _)| even_pos.contains(&pos) || odd_pos.contains(&pos)));
+    }
+
+    #[test]
+    fn test_diff() {
+        let before: GridMap<char> = ["#..", ".#.", "..#"].into_iter().collect();
+        let after: GridMap<char> = ["#..", ".#.", ".##"].into_iter().collect();
+
+        assert!(before.diff(&before).is_empty());
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.len(), 1);
+        let (pos, old, new) = diff[0];
+        assert_eq!(pos.as_xy(&before), (1, 2));
+        assert_eq!((*old, *new), ('.', '#'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_diff_shape_mismatch() {
+        let a: GridMap<char> = ["ab", "cd"].into_iter().collect();
+        let b: GridMap<char> = ["abc", "def"].into_iter().collect();
+        a.diff(&b);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let map: GridMap<u32> = ["123", "456"]
+            .into_iter()
+            .collect::<GridMap<char>>()
+            .map(|c: &char| c.to_digit(10).unwrap());
+
+        let row_sums: Vec<u32> = map.rows().map(|row| row.sum()).collect();
+        assert_eq!(row_sums, vec![6, 15]);
+
+        let col_sums: Vec<u32> = map.columns().map(|col| col.sum()).collect();
+        assert_eq!(col_sums, vec![5, 7, 9]);
+
+        let first_col: Vec<u32> =
+            map.columns().next().unwrap().copied().collect();
+        assert_eq!(first_col, vec![1, 4]);
+    }
+
+    #[test]
+    fn test_iter_ray_bounded() {
+        let map: GridMap<char> = ["abcde", "fghij", "klmno", "pqrst", "uvwxy"]
+            .into_iter()
+            .collect();
+        let start = map.top_left();
+
+        let ray = |step: (i64, i64), max_steps: usize| -> String {
+            map.iter_ray_bounded(start, step, max_steps)
+                .map(|(_, c)| *c)
+                .collect()
+        };
+
+        assert_eq!(ray((1, 1), 2), "agm");
+        assert_eq!(ray((1, 0), 0), "a");
+        assert_eq!(ray((0, 1), 10), "afkpu");
+        for max_steps in 0..10 {
+            assert!(ray((1, 1), max_steps).len() <= max_steps + 1);
+        }
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let map =
+            GridMap::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(map.shape(), (3, 2));
+        assert_eq!(map[(2, 0)], 3);
+        assert_eq!(map[(0, 1)], 4);
+
+        let ragged = GridMap::from_rows(vec![vec![1, 2, 3], vec![4, 5]]);
+        assert!(matches!(ragged, Err(GridMapError::InconsistentLineSize)));
+    }
+
+    #[test]
+    fn test_directional_search() {
+        let rows = [
+            "2413432311323",
+            "3215453535623",
+            "3255245654254",
+            "3446585845452",
+            "4546657867536",
+            "1438598798454",
+            "4457876987766",
+            "3637877979653",
+            "4654967986887",
+            "4564679986453",
+            "1224686865563",
+            "2546548887735",
+            "4322674655533",
+        ]
+        .into_iter()
+        .map(|line| {
+            line.chars()
+                .map(|c| c.to_digit(10).unwrap() as u8)
+                .collect()
+        })
+        .collect();
+        let heat_loss: GridMap<u8> = GridMap::from_rows(rows).unwrap();
+
+        let search = |min_run, max_run| {
+            heat_loss.directional_search(
+                (0, 0),
+                (12, 12),
+                min_run,
+                max_run,
+                |&val| val as u64,
+            )
+        };
+        assert_eq!(search(1, 3), Some(102));
+        assert_eq!(search(4, 10), Some(94));
+    }
+
+    #[test]
+    fn test_map_neighborhood() {
+        let life_step = |map: &GridMap<char>| -> GridMap<char> {
+            map.map_neighborhood(Adjacency::Queen, '.', |&cell, neighbors| {
+                let num_alive = neighbors.iter().filter(|&&c| c == '#').count();
+                match (cell, num_alive) {
+                    ('#', 2 | 3) | ('.', 3) => '#',
+                    _ => '.',
+                }
+            })
+        };
+
+        let blinker: GridMap<char> =
+            [".....", "..#..", "..#..", "..#..", "....."]
+                .into_iter()
+                .collect();
+        let rotated: GridMap<char> =
+            [".....", ".....", ".###.", ".....", "....."]
+                .into_iter()
+                .collect();
+        assert_eq!(life_step(&blinker), rotated);
+        assert_eq!(life_step(&rotated), blinker);
+
+        let block: GridMap<char> = ["##", "##"].into_iter().collect();
+        assert_eq!(life_step(&block), block);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::geometry::Vector;
+
+use std::ops::{Index, IndexMut};
+
+/// A dense 3-d grid, stored in a flat `Vec` with x varying fastest,
+/// then y, then z.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridMap3<T> {
+    x_size: usize,
+    y_size: usize,
+    z_size: usize,
+    values: Vec<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency3 {
+    /// The 6 cells that share a face.
+    Faces,
+    /// The 26 cells that share a face, edge, or corner.
+    All,
+}
+
+impl Adjacency3 {
+    pub fn offsets(&self) -> impl Iterator<Item = Vector<3, i64>> {
+        let include_diagonals = matches!(self, Adjacency3::All);
+        (-1..=1)
+            .flat_map(|dz| (-1..=1).map(move |dy| (dy, dz)))
+            .flat_map(|(dy, dz)| (-1..=1).map(move |dx| [dx, dy, dz]))
+            .filter(move |offset| {
+                let num_nonzero = offset.iter().filter(|&&d| d != 0).count();
+                num_nonzero == 1 || (include_diagonals && num_nonzero > 1)
+            })
+            .map(Vector::new)
+    }
+}
+
+impl<T> GridMap3<T> {
+    pub fn new_uniform(
+        x_size: usize,
+        y_size: usize,
+        z_size: usize,
+        value: T,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            x_size,
+            y_size,
+            z_size,
+            values: vec![value; x_size * y_size * z_size],
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize, usize) {
+        (self.x_size, self.y_size, self.z_size)
+    }
+
+    pub fn shape_vec(&self) -> Vector<3, i64> {
+        [self.x_size as i64, self.y_size as i64, self.z_size as i64].into()
+    }
+
+    fn flat_index(&self, pos: Vector<3, i64>) -> Option<usize> {
+        let in_bounds = pos
+            .iter()
+            .zip(self.shape_vec().iter())
+            .all(|(&coord, &size)| 0 <= coord && coord < size);
+        in_bounds.then(|| {
+            let x = pos.x() as usize;
+            let y = pos.y() as usize;
+            let z = pos.z() as usize;
+            (z * self.y_size + y) * self.x_size + x
+        })
+    }
+
+    pub fn is_valid(&self, pos: Vector<3, i64>) -> bool {
+        self.flat_index(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Vector<3, i64>) -> Option<&T> {
+        self.flat_index(pos).map(|index| &self.values[index])
+    }
+
+    pub fn get_mut(&mut self, pos: Vector<3, i64>) -> Option<&mut T> {
+        self.flat_index(pos).map(|index| &mut self.values[index])
+    }
+
+    /// In-bounds neighbors of the position.
+    pub fn adjacent_points(
+        &self,
+        pos: Vector<3, i64>,
+        adj: Adjacency3,
+    ) -> impl Iterator<Item = Vector<3, i64>> + '_ {
+        adj.offsets()
+            .map(move |offset| pos + offset)
+            .filter(|&new_pos| self.is_valid(new_pos))
+    }
+
+    pub fn iter_pos(&self) -> impl Iterator<Item = (Vector<3, i64>, &T)> {
+        let (x_size, y_size, _) = self.shape();
+        self.values.iter().enumerate().map(move |(index, value)| {
+            let x = index % x_size;
+            let y = (index / x_size) % y_size;
+            let z = index / (x_size * y_size);
+            let pos = [x as i64, y as i64, z as i64].into();
+            (pos, value)
+        })
+    }
+}
+
+impl<T> Index<Vector<3, i64>> for GridMap3<T> {
+    type Output = T;
+    fn index(&self, pos: Vector<3, i64>) -> &T {
+        self.get(pos).unwrap()
+    }
+}
+
+impl<T> IndexMut<Vector<3, i64>> for GridMap3<T> {
+    fn index_mut(&mut self, pos: Vector<3, i64>) -> &mut T {
+        self.get_mut(pos).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Adjacency3::Faces.offsets().count(), 6);
+        assert_eq!(Adjacency3::All.offsets().count(), 26);
+        assert!(Adjacency3::All
+            .offsets()
+            .all(|offset| offset != Vector::zero()));
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut grid = GridMap3::new_uniform(3, 3, 3, 0);
+        grid[[1, 2, 0].into()] = 5;
+        assert_eq!(grid.get([1, 2, 0].into()), Some(&5));
+        assert_eq!(grid.get([2, 1, 0].into()), Some(&0));
+        assert_eq!(grid.get([3, 0, 0].into()), None);
+        assert_eq!(grid.get([0, 0, -1].into()), None);
+
+        let (pos, _) = grid.iter_pos().find(|(_, &value)| value == 5).unwrap();
+        assert_eq!(pos, [1, 2, 0].into());
+    }
+
+    #[test]
+    fn test_adjacent_points() {
+        let grid = GridMap3::new_uniform(3, 3, 3, ());
+        let center: Vector<3, i64> = [1, 1, 1].into();
+        let corner: Vector<3, i64> = [0, 0, 0].into();
+        let edge: Vector<3, i64> = [1, 0, 0].into();
+
+        let count = |pos, adj| grid.adjacent_points(pos, adj).count();
+        assert_eq!(count(center, Adjacency3::All), 26);
+        assert_eq!(count(center, Adjacency3::Faces), 6);
+        assert_eq!(count(corner, Adjacency3::All), 7);
+        assert_eq!(count(corner, Adjacency3::Faces), 3);
+        assert_eq!(count(edge, Adjacency3::All), 11);
+        assert_eq!(count(edge, Adjacency3::Faces), 4);
+    }
+}
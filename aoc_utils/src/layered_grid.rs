@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use crate::geometry::Vector;
+
+/// A toroidal 2-d grid holding several layers of moving entities,
+/// such as the blizzards of 2022 day 24.  Every entity within a layer
+/// moves with the layer's velocity on each step, wrapping around the
+/// edges of the grid.  Multiple entities may occupy the same cell.
+#[derive(Debug, Clone)]
+pub struct LayeredGrid<L> {
+    x_size: usize,
+    y_size: usize,
+    time: usize,
+    layers: Vec<GridLayer<L>>,
+}
+
+#[derive(Debug, Clone)]
+struct GridLayer<L> {
+    label: L,
+    velocity: Vector<2, i64>,
+    positions: HashSet<Vector<2, i64>>,
+}
+
+impl<L> LayeredGrid<L> {
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        assert!(x_size > 0 && y_size > 0, "LayeredGrid must be non-empty");
+        Self {
+            x_size,
+            y_size,
+            time: 0,
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.x_size, self.y_size)
+    }
+
+    /// The number of steps taken since the grid was created.
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    /// The number of steps after which every layer returns to its
+    /// initial positions.  Since all velocities are integers, this is
+    /// the least common multiple of the grid dimensions.
+    pub fn period(&self) -> usize {
+        num::integer::lcm(self.x_size, self.y_size)
+    }
+
+    fn wrap(&self, pos: Vector<2, i64>) -> Vector<2, i64> {
+        [
+            pos.x().rem_euclid(self.x_size as i64),
+            pos.y().rem_euclid(self.y_size as i64),
+        ]
+        .into()
+    }
+
+    /// Add a layer of entities, all moving with the same velocity.
+    /// Positions outside of the grid are wrapped onto it.
+    pub fn add_layer(
+        &mut self,
+        label: L,
+        velocity: impl Into<Vector<2, i64>>,
+        positions: impl IntoIterator<Item = Vector<2, i64>>,
+    ) {
+        let positions =
+            positions.into_iter().map(|pos| self.wrap(pos)).collect();
+        self.layers.push(GridLayer {
+            label,
+            velocity: velocity.into(),
+            positions,
+        });
+    }
+
+    /// Advance every layer by one step.
+    pub fn step(&mut self) {
+        let (x_size, y_size) = (self.x_size as i64, self.y_size as i64);
+        self.layers.iter_mut().for_each(|layer| {
+            let velocity = layer.velocity;
+            layer.positions = layer
+                .positions
+                .iter()
+                .map(|&pos| pos + velocity)
+                .map(|pos| {
+                    [pos.x().rem_euclid(x_size), pos.y().rem_euclid(y_size)]
+                        .into()
+                })
+                .collect();
+        });
+        self.time += 1;
+    }
+
+    /// The labels of all layers with an entity at the given position.
+    pub fn labels_at(
+        &self,
+        pos: impl Into<Vector<2, i64>>,
+    ) -> impl Iterator<Item = &L> + '_ {
+        let pos = self.wrap(pos.into());
+        self.layers
+            .iter()
+            .filter(move |layer| layer.positions.contains(&pos))
+            .map(|layer| &layer.label)
+    }
+
+    /// Whether any layer has an entity at the given position.
+    pub fn is_occupied(&self, pos: impl Into<Vector<2, i64>>) -> bool {
+        self.labels_at(pos).next().is_some()
+    }
+
+    /// Iterate over all entities, along with the label of the layer
+    /// that contains each entity.
+    pub fn iter_entities(
+        &self,
+    ) -> impl Iterator<Item = (&L, Vector<2, i64>)> + '_ {
+        self.layers.iter().flat_map(|layer| {
+            layer.positions.iter().map(move |&pos| (&layer.label, pos))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_blizzard_wraps() {
+        let mut grid = LayeredGrid::new(4, 3);
+        grid.add_layer('>', [1, 0], [Vector::new([2, 1])]);
+        assert_eq!(grid.period(), 12);
+
+        let positions: Vec<(i64, i64)> = (0..4)
+            .map(|_| {
+                grid.step();
+                let (_, pos) = grid.iter_entities().next().unwrap();
+                pos.into()
+            })
+            .collect();
+        assert_eq!(positions, vec![(3, 1), (0, 1), (1, 1), (2, 1)]);
+        assert!(grid.is_occupied((2, 1)));
+        assert_eq!(grid.time(), 4);
+    }
+
+    #[test]
+    fn test_layers_return_after_period() {
+        let mut grid = LayeredGrid::new(4, 3);
+        grid.add_layer('>', [1, 0], [Vector::new([0, 0]), Vector::new([2, 1])]);
+        grid.add_layer('^', [0, -1], [Vector::new([3, 2])]);
+
+        let snapshot =
+            |grid: &LayeredGrid<char>| -> HashSet<(char, (i64, i64))> {
+                grid.iter_entities()
+                    .map(|(&label, pos)| (label, pos.into()))
+                    .collect()
+            };
+        let initial = snapshot(&grid);
+
+        for _ in 0..grid.period() {
+            grid.step();
+        }
+        assert_eq!(snapshot(&grid), initial);
+    }
+
+    #[test]
+    fn test_overlapping_entities() {
+        let mut grid = LayeredGrid::new(4, 4);
+        grid.add_layer('>', [1, 0], [Vector::new([0, 2])]);
+        grid.add_layer('v', [0, 1], [Vector::new([2, 0])]);
+        assert!(grid.labels_at((2, 2)).next().is_none());
+
+        grid.step();
+        grid.step();
+        let labels: Vec<char> = grid.labels_at((2, 2)).cloned().collect();
+        assert_eq!(labels, vec!['>', 'v']);
+        assert!(!grid.is_occupied((0, 2)));
+        assert!(grid.is_occupied((6, -2)));
+    }
+}
@@ -4,6 +4,15 @@ pub use numerics::*;
 mod gridmap;
 pub use gridmap::*;
 
+mod gridmap3;
+pub use gridmap3::*;
+
+mod bits;
+pub use bits::*;
+
+mod bitgrid;
+pub use bitgrid::BitGrid;
+
 pub mod extensions;
 
 pub mod direction;
@@ -20,4 +29,13 @@ pub use pause::*;
 mod fraction;
 pub use fraction::Fraction;
 
+mod layered_grid;
+pub use layered_grid::LayeredGrid;
+
+mod union_find;
+pub use union_find::UnionFind;
+
+mod slice_memo;
+pub use slice_memo::SliceMemo;
+
 pub mod prelude;
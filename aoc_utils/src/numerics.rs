@@ -1,3 +1,6 @@
+use aoc_framework::Error;
+use bit_set::BitSet;
+
 pub fn gcd(a: i32, b: i32) -> i32 {
     if b == 0 {
         a
@@ -10,6 +13,297 @@ pub fn lcm(a: i32, b: i32) -> i32 {
     a * b / gcd(a, b)
 }
 
+/// Returns `(g, x, y)`, such that `a*x + b*y == g`, where `g` is the
+/// non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The multiplicative inverse of `a`, modulo `m`, in the range
+/// `0..m`.  Returns None if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (gcd, x, _) = extended_gcd(a, m);
+    (gcd == 1).then(|| x.rem_euclid(m))
+}
+
+/// Combine a set of congruences `x = remainder (mod modulus)` into a
+/// single congruence, using the Chinese Remainder Theorem.  The
+/// moduli are not required to be coprime.  Returns the combined
+/// `(remainder, modulus)`, with `0 <= remainder < modulus`, or None if
+/// the congruences are inconsistent.
+pub fn crt(residues: &[(i64, i64)]) -> Option<(i64, i64)> {
+    residues.iter().try_fold(
+        (0, 1),
+        |(rem_a, mod_a): (i64, i64), &(rem_b, mod_b)| {
+            let (gcd, coef_a, _) = extended_gcd(mod_a, mod_b);
+            let diff = rem_b - rem_a;
+            if diff % gcd != 0 {
+                return None;
+            }
+
+            let lcm = (mod_a / gcd) as i128 * mod_b as i128;
+            let steps =
+                (diff / gcd) as i128 * coef_a as i128 % (mod_b / gcd) as i128;
+            let rem = (rem_a as i128 + steps * mod_a as i128).rem_euclid(lcm);
+            Some((rem.try_into().ok()?, lcm.try_into().ok()?))
+        },
+    )
+}
+
+/// The floor of the square root of `n`, computed exactly.
+pub fn isqrt(n: u64) -> u64 {
+    // The floating-point estimate may be off by one for large n, so
+    // adjust it to the exact value.
+    let mut root = (n as f64).sqrt() as u64;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1)
+        .checked_mul(root + 1)
+        .is_some_and(|square| square <= n)
+    {
+        root += 1;
+    }
+    root
+}
+
+pub fn is_perfect_square(n: u64) -> bool {
+    let root = isqrt(n);
+    root * root == n
+}
+
+/// `base` raised to the power `exp`, or None if the result would
+/// overflow.
+pub fn checked_ipow(base: i64, exp: u32) -> Option<i64> {
+    base.checked_pow(exp)
+}
+
+/// The floor of the base-10 logarithm of `n`, computed exactly
+/// without floating-point rounding.  One less than the number of
+/// decimal digits in `n`.  Panics if `n` is zero.
+pub fn ilog10(n: u64) -> u32 {
+    n.ilog10()
+}
+
+/// The `n`-th triangular number, `1 + 2 + ... + n`.
+pub fn triangular(n: u64) -> u64 {
+    checked_triangular(n).expect("Overflow computing triangular number")
+}
+
+fn checked_triangular(n: u64) -> Option<u64> {
+    // Halve whichever factor is even, to avoid overflowing the
+    // intermediate product.
+    if n.is_multiple_of(2) {
+        (n / 2).checked_mul(n.checked_add(1)?)
+    } else {
+        n.checked_mul(n.div_ceil(2))
+    }
+}
+
+/// The largest `k` such that `triangular(k) <= n`.  For a triangular
+/// number, this is the inverse of `triangular`.
+pub fn triangular_root(n: u64) -> u64 {
+    let fits = |k| checked_triangular(k).is_some_and(|tri| tri <= n);
+
+    // The floating-point estimate may be off by one for large n, so
+    // adjust it to the exact value.
+    let mut root = (2.0 * n as f64).sqrt() as u64;
+    while !fits(root) {
+        root -= 1;
+    }
+    while fits(root + 1) {
+        root += 1;
+    }
+    root
+}
+
+/// The sum of all integers in the inclusive range `a..=b`, or zero if
+/// the range is empty.
+pub fn sum_range(a: u64, b: u64) -> u64 {
+    if a > b {
+        0
+    } else if a == 0 {
+        triangular(b)
+    } else {
+        triangular(b) - triangular(a - 1)
+    }
+}
+
+/// The digits of `n` in the given radix, starting with the most
+/// significant digit.  Zero has a single digit.
+pub fn digits(n: u64, radix: u32) -> impl Iterator<Item = u32> {
+    let radix = radix as u64;
+    let least_significant_first: Vec<u32> =
+        std::iter::successors(Some(n), |&rest| {
+            (rest >= radix).then_some(rest / radix)
+        })
+        .map(|rest| (rest % radix) as u32)
+        .collect();
+    least_significant_first.into_iter().rev()
+}
+
+/// The number represented by the digits in the given radix, starting
+/// with the most significant digit.  The inverse of `digits`.
+pub fn from_digits(digits: impl IntoIterator<Item = u32>, radix: u32) -> u64 {
+    digits
+        .into_iter()
+        .fold(0, |acc, digit| acc * (radix as u64) + (digit as u64))
+}
+
+/// Parse an integer written in the given radix, with an optional
+/// leading `-`.  Digits above 9 are written as letters, in either
+/// case, so the radix may be at most 36.
+pub fn from_radix_str(s: &str, radix: u32) -> Result<i64, Error> {
+    let (is_negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    if digits.is_empty() {
+        return Err(Error::InvalidString(s.to_string()));
+    }
+
+    let magnitude = digits.chars().try_fold(0i64, |acc, c| {
+        let digit = c.to_digit(radix).ok_or(Error::UnknownChar(c))?;
+        acc.checked_mul(radix as i64)
+            .and_then(|acc| acc.checked_add(digit as i64))
+            .ok_or_else(|| Error::InvalidString(s.to_string()))
+    })?;
+
+    Ok(if is_negative { -magnitude } else { magnitude })
+}
+
+/// Format an integer in the given radix, using lowercase letters for
+/// digits above 9.  The inverse of `from_radix_str`.
+pub fn to_radix_str(n: i64, radix: u32) -> String {
+    let magnitude: String = digits(n.unsigned_abs(), radix)
+        .map(|digit| std::char::from_digit(digit, radix).unwrap())
+        .collect();
+    if n < 0 {
+        format!("-{magnitude}")
+    } else {
+        magnitude
+    }
+}
+
+/// Parse an integer written with a custom set of digits, such as a
+/// balanced base with negative digit values.  Each entry of `digits`
+/// maps a character to its value, and the radix is the number of
+/// digits.
+pub fn from_balanced_str(
+    s: &str,
+    digits: &[(char, i64)],
+) -> Result<i64, Error> {
+    let radix = digits.len() as i64;
+    s.chars().try_fold(0, |acc, c| {
+        let value = digits
+            .iter()
+            .find(|(digit, _)| *digit == c)
+            .map(|(_, value)| *value)
+            .ok_or(Error::UnknownChar(c))?;
+        Ok(acc * radix + value)
+    })
+}
+
+/// Format an integer with a custom set of digits.  The inverse of
+/// `from_balanced_str`.  The digit values must cover every residue
+/// modulo the radix exactly once.
+pub fn to_balanced_str(n: i64, digits: &[(char, i64)]) -> String {
+    let radix = digits.len() as i64;
+    let digit_for = |residue: i64| {
+        digits
+            .iter()
+            .find(|(_, value)| value.rem_euclid(radix) == residue)
+            .copied()
+            .expect("Digits must cover all residues of the radix")
+    };
+
+    if n == 0 {
+        return digit_for(0).0.to_string();
+    }
+
+    let mut remainder = n;
+    let least_significant_first: Vec<char> = std::iter::from_fn(|| {
+        (remainder != 0).then(|| {
+            let (c, value) = digit_for(remainder.rem_euclid(radix));
+            remainder = (remainder - value) / radix;
+            c
+        })
+    })
+    .collect();
+    least_significant_first.into_iter().rev().collect()
+}
+
+/// All primes less than or equal to `n`, found with a sieve of
+/// Eratosthenes.
+pub fn primes_up_to(n: usize) -> Vec<usize> {
+    let mut composite = BitSet::with_capacity(n + 1);
+    (2..=n)
+        .filter(|&i| {
+            if composite.contains(i) {
+                return false;
+            }
+            (i.saturating_mul(i)..=n).step_by(i).for_each(|multiple| {
+                composite.insert(multiple);
+            });
+            true
+        })
+        .collect()
+}
+
+/// Deterministic Miller-Rabin primality test, valid for all `u64`.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    if let Some(&witness) = WITNESSES.iter().find(|&&w| n.is_multiple_of(w)) {
+        return n == witness;
+    }
+
+    let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % n as u128) as u64;
+    let pow_mod = |mut base: u64, mut exp: u64| {
+        let mut result = 1;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                result = mul_mod(result, base);
+            }
+            base = mul_mod(base, base);
+            exp /= 2;
+        }
+        result
+    };
+
+    // n - 1 = d * 2^s, with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    WITNESSES.iter().all(|&witness| {
+        let mut x = pow_mod(witness, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = mul_mod(x, x);
+            x == n - 1
+        })
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -29,4 +323,186 @@ mod test {
         assert_eq!(lcm(13, -35), -455);
         assert_eq!(lcm(-4000, 35), -28000);
     }
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(29), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(1), Vec::<usize>::new());
+        assert_eq!(primes_up_to(2), vec![2]);
+        assert_eq!(primes_up_to(10_000).len(), 1229);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let first_ten: Vec<u64> = (0..30).filter(|&n| is_prime(n)).collect();
+        assert_eq!(first_ten, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+
+        assert!(!is_prime(561)); // Carmichael number
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(18_446_744_073_709_551_557)); // Largest u64 prime
+
+        let sieved: Vec<u64> =
+            primes_up_to(10_000).into_iter().map(|p| p as u64).collect();
+        let tested: Vec<u64> = (0..=10_000).filter(|&n| is_prime(n)).collect();
+        assert_eq!(sieved, tested);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(
+            crt(&[(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
+            Some((1068781, 3162341))
+        );
+        assert_eq!(crt(&[(-1, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in
+            [(240, 46), (46, 240), (-240, 46), (17, 5), (0, 7), (7, 0)]
+        {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a as i32, b as i32).abs() as i64);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(48), 6);
+        assert_eq!(isqrt(49), 7);
+        assert_eq!(isqrt(50), 7);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+
+        let big_root: u64 = 3_037_000_499;
+        assert_eq!(isqrt(big_root * big_root), big_root);
+        assert_eq!(isqrt(big_root * big_root - 1), big_root - 1);
+    }
+
+    #[test]
+    fn test_is_perfect_square() {
+        let squares: Vec<u64> =
+            (0..50).filter(|&n| is_perfect_square(n)).collect();
+        assert_eq!(squares, vec![0, 1, 4, 9, 16, 25, 36, 49]);
+        assert!(is_perfect_square(1 << 62));
+        assert!(!is_perfect_square((1 << 62) + 1));
+    }
+
+    #[test]
+    fn test_checked_ipow() {
+        assert_eq!(checked_ipow(10, 0), Some(1));
+        assert_eq!(checked_ipow(10, 18), Some(1_000_000_000_000_000_000));
+        assert_eq!(checked_ipow(10, 19), None);
+        assert_eq!(checked_ipow(-2, 63), Some(i64::MIN));
+        assert_eq!(checked_ipow(2, 63), None);
+        assert_eq!(checked_ipow(-3, 3), Some(-27));
+    }
+
+    #[test]
+    fn test_ilog10() {
+        assert_eq!(ilog10(1), 0);
+        assert_eq!(ilog10(9), 0);
+        assert_eq!(ilog10(10), 1);
+        assert_eq!(ilog10(999), 2);
+        assert_eq!(ilog10(1000), 3);
+        assert_eq!(ilog10(999_999_999_999_999_999), 17);
+        assert_eq!(ilog10(1_000_000_000_000_000_000), 18);
+        assert_eq!(ilog10(u64::MAX), 19);
+    }
+
+    #[test]
+    fn test_triangular() {
+        let expected = [0, 1, 3, 6, 10, 15, 21];
+        expected.iter().enumerate().for_each(|(n, &tri)| {
+            assert_eq!(triangular(n as u64), tri);
+        });
+        assert_eq!(triangular(u32::MAX as u64), 9223372034707292160);
+    }
+
+    #[test]
+    fn test_triangular_root() {
+        (0..1000).for_each(|n| assert_eq!(triangular_root(triangular(n)), n));
+        assert_eq!(triangular_root(9), 3);
+        assert_eq!(triangular_root(10), 4);
+        assert_eq!(triangular_root(u64::MAX), 6074000999);
+    }
+
+    #[test]
+    fn test_sum_range() {
+        assert_eq!(sum_range(1, 10), 55);
+        assert_eq!(sum_range(0, 10), 55);
+        assert_eq!(sum_range(5, 7), 18);
+        assert_eq!(sum_range(7, 7), 7);
+        assert_eq!(sum_range(8, 7), 0);
+    }
+
+    #[test]
+    fn test_radix_str() {
+        assert_eq!(from_radix_str("101", 2).unwrap(), 5);
+        assert_eq!(from_radix_str("ff", 16).unwrap(), 255);
+        assert_eq!(from_radix_str("-FF", 16).unwrap(), -255);
+        assert_eq!(to_radix_str(255, 16), "ff");
+        assert_eq!(to_radix_str(-5, 2), "-101");
+        assert_eq!(to_radix_str(0, 7), "0");
+
+        assert!(from_radix_str("12", 2).is_err());
+        assert!(from_radix_str("", 10).is_err());
+        assert!(from_radix_str("-", 10).is_err());
+
+        [0, 1, -1, 35, -36, 123456789, i64::MAX, i64::MIN + 1]
+            .into_iter()
+            .for_each(|n| {
+                [2, 3, 10, 16, 36].into_iter().for_each(|radix| {
+                    let s = to_radix_str(n, radix);
+                    assert_eq!(from_radix_str(&s, radix).unwrap(), n);
+                })
+            });
+    }
+
+    #[test]
+    fn test_balanced_str() {
+        let snafu = [('=', -2), ('-', -1), ('0', 0), ('1', 1), ('2', 2)];
+        assert_eq!(from_balanced_str("1=-0-2", &snafu).unwrap(), 1747);
+        assert_eq!(to_balanced_str(1747, &snafu), "1=-0-2");
+        assert_eq!(to_balanced_str(0, &snafu), "0");
+        assert_eq!(to_balanced_str(-1, &snafu), "-");
+        assert!(from_balanced_str("13", &snafu).is_err());
+
+        (-200..=200).for_each(|n| {
+            let s = to_balanced_str(n, &snafu);
+            assert_eq!(from_balanced_str(&s, &snafu).unwrap(), n);
+        });
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(12345, 10).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(digits(0, 10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits(10, 2).collect::<Vec<_>>(), vec![1, 0, 1, 0]);
+        assert_eq!(digits(255, 16).collect::<Vec<_>>(), vec![15, 15]);
+        assert_eq!(digits(u64::MAX, 10).count(), 20);
+    }
+
+    #[test]
+    fn test_digits_round_trip() {
+        for radix in [2, 3, 10, 16, 36] {
+            for n in [0, 1, 9, 10, 35, 36, 1000, 123_456_789, u64::MAX] {
+                assert_eq!(from_digits(digits(n, radix), radix), n);
+            }
+        }
+    }
 }
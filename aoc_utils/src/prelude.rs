@@ -4,9 +4,14 @@ pub use itertools::Itertools as _;
 
 pub use crate::extensions::CharIterLocExt as _;
 pub use crate::extensions::CollectBits as _;
+pub use crate::extensions::DedupByKey as _;
 pub use crate::extensions::ExactlyOneExt as _;
+pub use crate::extensions::FoldGrouped as _;
+pub use crate::extensions::JoinWith as _;
+pub use crate::extensions::MinMaxByKey as _;
 pub use crate::extensions::RangeIntersection as _;
 pub use crate::extensions::RangeIntersects as _;
+pub use crate::extensions::RangeMerge as _;
 pub use crate::extensions::TakeWhileInclusive as _;
 
 pub use crate::geometry::{Matrix, Vector};
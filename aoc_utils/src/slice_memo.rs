@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+/// Memoization for recursive functions over suffixes of two slices,
+/// such as edit distance or counting arrangements of a pattern.
+///
+/// The function being memoized receives the `SliceMemo` along with
+/// the current suffix of each slice, and may recurse by calling
+/// `SliceMemo::eval` on shorter suffixes.  Results are cached by the
+/// offset of each suffix within the original slices, so each pair of
+/// suffixes is evaluated at most once.  The function must be declared
+/// with the same lifetime for the memo and both slices, e.g.
+/// `fn func<'a>(memo: &mut SliceMemo<'a, A, B, V>, a: &'a [A], b: &'a
+/// [B]) -> V`.
+pub struct SliceMemo<'a, A, B, V> {
+    a: &'a [A],
+    b: &'a [B],
+    func: MemoFunc<'a, A, B, V>,
+    cache: HashMap<(usize, usize), V>,
+}
+
+type MemoFunc<'a, A, B, V> =
+    fn(&mut SliceMemo<'a, A, B, V>, &'a [A], &'a [B]) -> V;
+
+impl<'a, A, B, V> SliceMemo<'a, A, B, V>
+where
+    V: Clone,
+{
+    pub fn new(a: &'a [A], b: &'a [B], func: MemoFunc<'a, A, B, V>) -> Self {
+        Self {
+            a,
+            b,
+            func,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Evaluate the function over the full slices.
+    pub fn eval_all(&mut self) -> V {
+        self.eval(self.a, self.b)
+    }
+
+    /// Evaluate the function over the given suffixes, returning the
+    /// cached result if one exists.  Each argument must be a suffix of
+    /// the corresponding slice passed to `SliceMemo::new`.
+    pub fn eval(&mut self, a: &'a [A], b: &'a [B]) -> V {
+        debug_assert!(
+            std::ptr::eq(a.as_ptr_range().end, self.a.as_ptr_range().end)
+                && std::ptr::eq(
+                    b.as_ptr_range().end,
+                    self.b.as_ptr_range().end
+                ),
+            "SliceMemo arguments must be suffixes of the original slices"
+        );
+        let key = (self.a.len() - a.len(), self.b.len() - b.len());
+
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = (self.func)(self, a, b);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The number of distinct pairs of suffixes evaluated so far.
+    pub fn num_cached(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit_distance<'a>(
+        memo: &mut SliceMemo<'a, char, char, usize>,
+        a: &'a [char],
+        b: &'a [char],
+    ) -> usize {
+        match (a.split_first(), b.split_first()) {
+            (None, _) => b.len(),
+            (_, None) => a.len(),
+            (Some((c_a, rest_a)), Some((c_b, rest_b))) => {
+                if c_a == c_b {
+                    memo.eval(rest_a, rest_b)
+                } else {
+                    let substitute = memo.eval(rest_a, rest_b);
+                    let delete = memo.eval(rest_a, b);
+                    let insert = memo.eval(a, rest_b);
+                    1 + substitute.min(delete).min(insert)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let a: Vec<char> = "kitten".chars().collect();
+        let b: Vec<char> = "sitting".chars().collect();
+        let mut memo = SliceMemo::new(&a, &b, edit_distance);
+        assert_eq!(memo.eval_all(), 3);
+        assert!(memo.num_cached() <= (a.len() + 1) * (b.len() + 1));
+
+        let empty: Vec<char> = Vec::new();
+        assert_eq!(SliceMemo::new(&a, &empty, edit_distance).eval_all(), 6);
+    }
+
+    // Number of ways to fill in the unknown '?' springs, such that
+    // the runs of damaged '#' springs have the given lengths.
+    fn count_arrangements<'a>(
+        memo: &mut SliceMemo<'a, char, usize, u64>,
+        springs: &'a [char],
+        runs: &'a [usize],
+    ) -> u64 {
+        let Some((&run, remaining_runs)) = runs.split_first() else {
+            return if springs.contains(&'#') { 0 } else { 1 };
+        };
+
+        let as_operational = match springs.first() {
+            None => return 0,
+            Some('.' | '?') => memo.eval(&springs[1..], runs),
+            Some(_) => 0,
+        };
+
+        let as_damaged = if springs.len() >= run
+            && !springs[..run].contains(&'.')
+            && springs.get(run) != Some(&'#')
+        {
+            let next = (run + 1).min(springs.len());
+            memo.eval(&springs[next..], remaining_runs)
+        } else {
+            0
+        };
+
+        as_operational + as_damaged
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        let count = |springs: &str, runs: &[usize]| -> u64 {
+            let springs: Vec<char> = springs.chars().collect();
+            SliceMemo::new(&springs, runs, count_arrangements).eval_all()
+        };
+        assert_eq!(count("???.###", &[1, 1, 3]), 1);
+        assert_eq!(count(".??..??...?##.", &[1, 1, 3]), 4);
+        assert_eq!(count("?###????????", &[3, 2, 1]), 10);
+    }
+}
@@ -1,4 +1,5 @@
 use aoc_utils::prelude::*;
+use aoc_utils::triangular;
 
 #[derive(aoc_macros::YearDay)]
 pub struct ThisDay;
@@ -54,10 +55,7 @@ impl Puzzle for ThisDay {
             .map(|pos| {
                 crab_pos
                     .iter()
-                    .map(|c| {
-                        let steps = (c - pos).abs();
-                        steps * (steps + 1) / 2
-                    })
+                    .map(|c| triangular((c - pos).unsigned_abs()) as i64)
                     .sum::<i64>()
             })
             .min()
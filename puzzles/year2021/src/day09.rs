@@ -57,7 +57,7 @@ impl std::str::FromStr for HeightMap {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(HeightMap {
-            map: s.lines().collect(),
+            map: GridMap::parse_digits(s.lines())?,
         })
     }
 }
@@ -70,7 +70,7 @@ impl Puzzle for ThisDay {
         lines: impl Iterator<Item = &'a str>,
     ) -> Result<Self::ParsedInput, Error> {
         Ok(HeightMap {
-            map: lines.collect(),
+            map: GridMap::parse_digits(lines)?,
         })
     }
 
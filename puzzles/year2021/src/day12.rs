@@ -481,6 +481,14 @@ impl TraversalState {
 impl Puzzle for ThisDay {
     const EXAMPLE_NUM: u8 = 0;
 
+    fn examples() -> Vec<ExampleSpec> {
+        vec![
+            ExampleSpec::new(0).expect_part_1("10").expect_part_2("36"),
+            ExampleSpec::new(3).expect_part_1("19").expect_part_2("103"),
+            ExampleSpec::new(5).expect_part_1("226").expect_part_2("3509"),
+        ]
+    }
+
     type ParsedInput = CaveSystem;
     fn parse_input<'a>(
         lines: impl Iterator<Item = &'a str>,
@@ -79,7 +79,7 @@ impl Puzzle for ThisDay {
         lines: impl Iterator<Item = &'a str>,
     ) -> Result<Self::ParsedInput, Error> {
         Ok(RiskMap {
-            grid: lines.collect(),
+            grid: GridMap::parse_digits(lines)?,
         })
     }
 
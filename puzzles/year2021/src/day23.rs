@@ -119,7 +119,7 @@ impl Display for AmphipodDiagram {
             .map(|(_line_num, char_vec)| -> String {
                 char_vec.into_iter().collect()
             })
-            .join("\n");
+            .collect_lines();
 
         write!(f, "{as_text}")
     }
@@ -662,9 +662,9 @@ impl Puzzle for ThisDay {
         let initial: AmphipodLayout = parsed.into();
         let target = initial.target_arrangement()?;
 
-        let path = initial.shortest_path(initial.clone(), target)?;
-
-        Ok(path.into_iter().map(|(_state, cost)| cost).sum::<u64>())
+        initial
+            .shortest_path_length(initial.clone(), target)
+            .ok_or(Error::NoPathToDest)
     }
 
     fn part_2(
@@ -675,8 +675,8 @@ impl Puzzle for ThisDay {
         let initial: AmphipodLayout = diagram.into();
         let target = initial.target_arrangement()?;
 
-        let path = initial.shortest_path(initial.clone(), target)?;
-
-        Ok(path.into_iter().map(|(_state, cost)| cost).sum::<u64>())
+        initial
+            .shortest_path_length(initial.clone(), target)
+            .ok_or(Error::NoPathToDest)
     }
 }
@@ -1,4 +1,5 @@
 use aoc_utils::prelude::*;
+use aoc_utils::checked_ipow;
 
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
@@ -70,7 +71,9 @@ impl ChecksumGraph {
         } else {
             10 - digit
         };
-        let cost = digit_cost * 10i64.pow(rounds_remaining as u32);
+        let place_value = checked_ipow(10, rounds_remaining as u32)
+            .expect("Serial number too long to represent");
+        let cost = digit_cost * place_value;
         cost as u64
     }
 
@@ -1,6 +1,6 @@
 use aoc_utils::prelude::*;
 
-use crate::utils::{Adjacency, GridMap};
+use crate::utils::GridMap;
 
 #[derive(aoc_macros::YearDay)]
 pub struct ThisDay;
@@ -19,14 +19,9 @@ impl Puzzle for ThisDay {
         gridmap: &Self::ParsedInput,
     ) -> Result<impl std::fmt::Debug, Error> {
         Ok(gridmap
-            .iter()
-            .filter(|&(pos, &height)| {
-                Adjacency::Rook.offsets().any(|offset| {
-                    gridmap.iter_ray(pos, offset).skip(1).all(
-                        |(_other_pos, &other_height)| height > other_height,
-                    )
-                })
-            })
+            .visible_from_edges(|height, other| other >= height)
+            .iter_item()
+            .filter(|&&visible| visible)
             .count())
     }
 
@@ -35,19 +30,8 @@ impl Puzzle for ThisDay {
     ) -> Result<impl std::fmt::Debug, Error> {
         Ok(gridmap
             .iter_pos()
-            .map(|(pos, &height)| {
-                Adjacency::Rook
-                    .offsets()
-                    .map(|offset| {
-                        gridmap
-                            .iter_ray(pos, offset)
-                            .skip(1)
-                            .take_while_inclusive(|(_, &other_height)| {
-                                other_height < height
-                            })
-                            .count()
-                    })
-                    .product::<usize>()
+            .map(|(pos, _)| {
+                gridmap.scenic_score(pos, |height, other| other >= height)
             })
             .max()
             .unwrap())
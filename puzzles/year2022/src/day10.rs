@@ -85,7 +85,7 @@ impl Puzzle for ThisDay {
                         .map(|is_lit| if is_lit { '#' } else { '.' })
                         .collect::<String>()
                 })
-                .join("\n")
+                .collect_lines()
                 .into();
         Ok(res)
     }
@@ -458,7 +458,7 @@ impl Puzzle for ThisDay {
         system: &Self::ParsedInput,
     ) -> Result<impl std::fmt::Debug, Error> {
         let initial_state = system.initial_state_part_1()?;
-        println!("Initial state: {initial_state:?}");
+        debug_println!("Initial state: {initial_state:?}");
 
         let idealized = initial_state.time_remaining * system.max_flow_rate();
         let orderings: Vec<_> = system
@@ -496,7 +496,7 @@ impl Puzzle for ThisDay {
         })
         .join("\n\t=> ");
 
-        println!("Path for best: {path_str}");
+        debug_println!("Path for best: {path_str}");
 
         Ok(idealized - best.1.initial_to_node)
     }
@@ -506,7 +506,7 @@ impl Puzzle for ThisDay {
     ) -> Result<impl std::fmt::Debug, Error> {
         let initial_state = system.initial_state_part_2()?;
         let idealized = initial_state.time_remaining * system.max_flow_rate();
-        println!("Initial state: {initial_state:?}");
+        debug_println!("Initial state: {initial_state:?}");
 
         let orderings: Vec<_> = system
             .dijkstra_search(initial_state)
@@ -514,7 +514,7 @@ impl Puzzle for ThisDay {
             .enumerate()
             .inspect(|(i, (node, _))| {
                 if i % 100000 == 0 {
-                    println!("Examined {}, t={}", i, node.time_remaining)
+                    debug_println!("Examined {}, t={}", i, node.time_remaining)
                 }
             })
             .map(|(_i, node)| node)
@@ -554,7 +554,7 @@ impl Puzzle for ThisDay {
         })
         .join("\n\t=> ");
 
-        println!("Path for best: {path_str}");
+        debug_println!("Path for best: {path_str}");
 
         Ok(idealized - best.1.initial_to_node)
     }
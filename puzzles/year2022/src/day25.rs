@@ -1,4 +1,5 @@
 use aoc_utils::prelude::*;
+use aoc_utils::{from_balanced_str, to_balanced_str};
 
 use std::fmt::{Display, Formatter};
 
@@ -7,47 +8,21 @@ pub struct SnafuNumber {
     inner: i64,
 }
 
+const SNAFU_DIGITS: [(char, i64); 5] =
+    [('=', -2), ('-', -1), ('0', 0), ('1', 1), ('2', 2)];
+
 impl std::str::FromStr for SnafuNumber {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let inner = s
-            .chars()
-            .map(|c| match c {
-                '2' => Ok(2),
-                '1' => Ok(1),
-                '0' => Ok(0),
-                '-' => Ok(-1),
-                '=' => Ok(-2),
-                _ => Err(Error::UnknownChar(c)),
-            })
-            .fold_ok(0, |a: i64, b: i64| a * 5 + b)?;
+        let inner = from_balanced_str(s, &SNAFU_DIGITS)?;
         Ok(SnafuNumber { inner })
     }
 }
 
 impl Display for SnafuNumber {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut remainder = self.inner;
-        std::iter::from_fn(|| -> Option<char> {
-            (remainder != 0).then(|| {
-                let digit = remainder.rem_euclid(5);
-                let (digit, c) = match digit {
-                    0 => (0, '0'),
-                    1 => (1, '1'),
-                    2 => (2, '2'),
-                    3 => (-2, '='),
-                    4 => (-1, '-'),
-                    _ => panic!("Unknown digit: {}", digit),
-                };
-                remainder = (remainder - digit).div_euclid(5);
-                c
-            })
-        })
-        .collect::<Vec<_>>()
-        .into_iter()
-        .rev()
-        .try_for_each(|c| write!(f, "{c}"))
+        write!(f, "{}", to_balanced_str(self.inner, &SNAFU_DIGITS))
     }
 }
 
@@ -165,15 +165,7 @@ impl Puzzle for ThisDay {
     fn parse_input<'a>(
         lines: impl Iterator<Item = &'a str>,
     ) -> Result<Self::ParsedInput, Error> {
-        let map = lines
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(x, c)| (x, y, c.to_digit(10).unwrap() as u8))
-            })
-            .collect();
-        Ok(HeatLossMap(map))
+        Ok(HeatLossMap(GridMap::parse_digits(lines)?))
     }
 
     fn part_1(
@@ -123,7 +123,7 @@ fn num_tiles_contained(commands: &[Command]) -> i64 {
             let distance = *distance as i64;
             let dir = *dir;
 
-            *pos += distance * dir.as_vec();
+            *pos += distance * dir.to_vector();
 
             Some(*pos)
         })
@@ -231,7 +231,7 @@ impl System {
                         _ => true,
                     })
                     .for_each(|(module_state, i)| {
-                        println!("\t{i}: {module_state:?}")
+                        debug_println!("\t{i}: {module_state:?}")
                     });
 
                 return (*prev, seen.len());
@@ -527,9 +527,9 @@ impl Puzzle for ThisDay {
                     })
                     .map(|(i, _)| i)
             })
-            .inspect(|i| println!("{}", system.get_name(*i).unwrap()))
+            .inspect(|i| debug_println!("{}", system.get_name(*i).unwrap()))
             .map(|i| system.find_cycle(i))
-            .inspect(|i| println!("\t{i:?}"))
+            .inspect(|i| debug_println!("\t{i:?}"))
             .map(|(a, b)| b - a)
             .product::<usize>();
 
@@ -103,8 +103,8 @@ impl EdgeWeightedGraph<Vector<2, i64>> for GardenMap {
         &'a self,
         old_pos: &'a Vector<2, i64>,
     ) -> impl Iterator<Item = (Vector<2, i64>, u64)> + '_ {
-        Direction::iter_cardinal()
-            .map(move |dir| *old_pos + dir.as_vec())
+        Direction::iter_all()
+            .map(move |dir| *old_pos + dir.to_vector())
             .filter(|&new_pos| self.is_garden_tile(new_pos))
             .map(|new_pos| (new_pos, 1))
     }
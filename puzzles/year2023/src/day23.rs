@@ -108,10 +108,10 @@ impl ForestMap {
 
     fn is_crossroad(&self, pos: Vector<2, i64>) -> bool {
         matches!(self.map.get(pos), Some(Tile::Path)) && {
-            let num_connections = Direction::iter_cardinal()
+            let num_connections = Direction::iter_all()
                 .filter(|dir| {
                     self.map
-                        .get(pos + dir.as_vec())
+                        .get(pos + dir.to_vector())
                         .map(|tile| !matches!(tile, Tile::Forest))
                         .unwrap_or(false)
                 })
@@ -206,10 +206,10 @@ impl EdgeWeightedGraph<(Vector<2, i64>, bool)> for ForestMap {
         let seen_crossroad = *seen_crossroad;
         let is_crossroad = self.is_crossroad(*old_pos);
 
-        Direction::iter_cardinal()
+        Direction::iter_all()
             .filter(move |_| !(seen_crossroad && is_crossroad))
             .filter_map(|dir| {
-                let new_pos = *old_pos + dir.as_vec();
+                let new_pos = *old_pos + dir.to_vector();
                 self.map
                     .get(new_pos)
                     .filter(|tile| tile.is_allowed_dir(dir, self.follow_slopes))
@@ -1,7 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
+use aoc_utils::geometry::{is_point_ahead, LinearSystem};
 use aoc_utils::prelude::*;
-use num::integer::gcd as find_gcd;
 
 pub struct Storm {
     hail: Vec<Hail>,
@@ -71,221 +71,6 @@ where
     }
 }
 
-pub struct AffineLinearSpace<const N: usize, T> {
-    pub offset: Vector<N, T>,
-    pub basis_states: Vec<Vector<N, T>>,
-}
-
-#[derive(Clone)]
-pub struct AugmentedMatrix<const ROWS: usize, const COLS: usize, T> {
-    pub matrix: Matrix<ROWS, COLS, T>,
-    pub augment: Vector<ROWS, T>,
-}
-
-impl<const ROWS: usize, const COLS: usize, T> std::fmt::Display
-    for AugmentedMatrix<ROWS, COLS, T>
-where
-    T: Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let col_widths: [usize; COLS] = std::array::from_fn(|i| {
-            (0..ROWS)
-                .map(|j| format!("{}", self.matrix[(j, i)]).len())
-                .max()
-                .unwrap_or(0)
-        });
-        let aug_width = (0..ROWS)
-            .map(|j| format!("{}", self.augment[j]).len())
-            .max()
-            .unwrap_or(0);
-        let total_width =
-            col_widths.iter().map(|w| w + 2).sum::<usize>() + aug_width + 3;
-
-        writeln!(f, "┌{:width$}┐", "", width = total_width)?;
-        (0..ROWS).try_for_each(|j| {
-            write!(f, "|")?;
-            self.matrix[j]
-                .iter()
-                .zip(col_widths.iter())
-                .try_for_each(|(item, width)| write!(f, " {item:width$} "))?;
-            writeln!(f, "| {:aug_width$} |", self.augment[j])
-        })?;
-        writeln!(f, "└{:width$}┘", "", width = total_width)?;
-        Ok(())
-    }
-}
-
-impl<const ROWS: usize, const COLS: usize, T> AugmentedMatrix<ROWS, COLS, T> {
-    fn normalize_equation(&mut self, i: usize)
-    where
-        T: Copy,
-        T: num::Integer,
-        T: num::Signed,
-        T: Display,
-    {
-        // Not technically required here, but canceling out
-        // unnecessary factors in each row avoids some integer
-        // overflow cases.
-        if let Some(gcd) = self.matrix[i]
-            .iter()
-            .chain(std::iter::once(&self.augment[i]))
-            .cloned()
-            .reduce(find_gcd)
-        {
-            let leading_sign = self.matrix[i]
-                .iter()
-                .find(|val| !val.is_zero())
-                .map(|val| val.signum())
-                .unwrap_or(T::one());
-            let gcd = gcd * leading_sign;
-
-            if !gcd.is_zero() {
-                self.matrix[i] = self.matrix[i] / gcd;
-                self.augment[i] = self.augment[i] / gcd;
-            }
-        }
-    }
-
-    fn row_echelon_form(mut self) -> Self
-    where
-        T: Copy,
-        T: num::Integer,
-        T: num::Signed,
-        T: Display,
-    {
-        for row in 0..ROWS {
-            self.normalize_equation(row);
-        }
-
-        let mut row = 0;
-
-        for column in 0..COLS {
-            if let Some(nonzero_row_i) =
-                (row..ROWS).find(|&j| !self.matrix[j][column].is_zero())
-            {
-                if row != nonzero_row_i {
-                    self.matrix.swap_rows(row, nonzero_row_i);
-                    self.augment.swap(row, nonzero_row_i);
-                }
-
-                // This loop could be reduced to only cover (i+1..M)
-                // and still produce a correct row echelon form, since
-                // that would provide the leading zeros for later
-                // rows.  This way, the only step remaining for rref
-                // is to scale each row to have a leading value of
-                // one.  This isn't done by default, as it would also
-                // require changing the type from T to Fraction<T>.
-                for j in 0..ROWS {
-                    if row != j && !self.matrix[j][column].is_zero() {
-                        let a = self.matrix[row][column];
-                        let b = self.matrix[j][column];
-                        let gcd = find_gcd(a, b);
-
-                        self.matrix[j] = self.matrix[j] * (a / gcd)
-                            - self.matrix[row] * (b / gcd);
-
-                        self.augment[j] = self.augment[j] * (a / gcd)
-                            - self.augment[row] * (b / gcd);
-                        self.normalize_equation(j);
-                    }
-                }
-                row += 1;
-            }
-        }
-
-        self
-    }
-
-    fn solve_system(&self) -> Option<AffineLinearSpace<COLS, Fraction<T>>>
-    where
-        T: Copy,
-        T: num::Integer,
-        T: num::Signed,
-        T: Display,
-    {
-        let echelon_form = self.clone().row_echelon_form();
-        let AugmentedMatrix {
-            matrix: echelon_form,
-            augment: solution,
-        } = echelon_form;
-
-        let row_of_zeros: [bool; ROWS] = std::array::from_fn(|i| {
-            (0..COLS).all(|j| echelon_form[(i, j)].is_zero())
-        });
-
-        let rank = row_of_zeros.iter().map(|b| !b as usize).sum::<usize>();
-        assert!(rank <= ROWS);
-        assert!(rank <= COLS);
-
-        // If the system of equations is inconsistent, the LHS will
-        // contain a fully-canceled row, but the RHS will not cancel
-        // out entirely.
-        let is_consistent = solution
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| row_of_zeros[*i])
-            .all(|(_, t)| T::is_zero(t));
-
-        if !is_consistent {
-            return None;
-        }
-
-        // The rank of the matrix may be insufficient to constrain the
-        // system.  (Maybe I should bail out early if `M < N`?  The
-        // rank cannot exceed `min(M,N)`, so that would skip the row
-        // echelon form step.)
-        let _is_fully_constrained = rank == COLS;
-
-        let leading_terms: [Option<usize>; ROWS] = std::array::from_fn(|row| {
-            echelon_form[row]
-                .iter()
-                .enumerate()
-                .find(|(_, element)| !element.is_zero())
-                .map(|(col, _)| col)
-        });
-
-        // The leading non-zero term in each row is used to determine
-        // a point that lies within the solution space.
-        let offset: Vector<COLS, Fraction<T>> = (0..ROWS)
-            .filter_map(|row| {
-                leading_terms[row].map(|col| {
-                    let value = Fraction {
-                        num: solution[row],
-                        denom: echelon_form[(row, col)],
-                    };
-                    Vector::<COLS, _>::one_hot(col) * value.normalize()
-                })
-            })
-            .sum();
-
-        // Columns that do not contain a leading non-zero term are
-        // used to determine the basis vectors of the solution space.
-        let basis_states: Vec<Vector<COLS, Fraction<T>>> = (0..COLS)
-            .filter(|col| !leading_terms.iter().contains(&Some(*col)))
-            .map(|col| {
-                (0..ROWS)
-                    .filter_map(|row| {
-                        leading_terms[row].map(|leading_col| (row, leading_col))
-                    })
-                    .filter(|(_, leading_col)| *leading_col != col)
-                    .map(|(row, leading_col)| {
-                        Vector::<COLS, _>::one_hot(leading_col)
-                            * Fraction {
-                                num: -echelon_form[(row, leading_col)],
-                                denom: echelon_form[(row, col)],
-                            }
-                    })
-                    .fold(Vector::one_hot(col), |a, b| a + b)
-            })
-            .collect();
-
-        Some(AffineLinearSpace {
-            offset,
-            basis_states,
-        })
-    }
-}
-
 impl Storm {
     fn iter_pairs(&self) -> impl Iterator<Item = (Hail, Hail)> + '_ {
         self.hail.iter().cloned().tuple_combinations()
@@ -353,14 +138,8 @@ impl Hail {
         let y = (v1.y() * b - v2.y() * a) / d;
         let pos: Vector<2, Fraction<i128>> = [x, y].into();
 
-        let is_p1_future = (pos - p1)
-            .into_iter()
-            .zip(v1.into_iter())
-            .all(|(delta, v)| delta * v >= 0.into());
-        let is_p2_future = (pos - p2)
-            .into_iter()
-            .zip(v2.into_iter())
-            .all(|(delta, v)| delta * v >= 0.into());
+        let is_p1_future = is_point_ahead(p1, v1, pos);
+        let is_p2_future = is_point_ahead(p2, v2, pos);
 
         if is_p1_future && is_p2_future {
             Some(pos)
@@ -403,19 +182,19 @@ impl Puzzle for ThisDay {
     fn part_1(
         storm: &Self::ParsedInput,
     ) -> Result<impl std::fmt::Debug, Error> {
-        let test_area: std::ops::RangeInclusive<Fraction<i128>> =
-            if storm.hail.len() == 5 {
-                7.into()..=27.into()
-            } else {
-                200000000000000.into()..=400000000000000.into()
-            };
+        let (area_min, area_max): (i128, i128) = if storm.hail.len() == 5 {
+            (7, 27)
+        } else {
+            (200000000000000, 400000000000000)
+        };
+        let in_test_area =
+            |val: Fraction<i128>| val >= area_min && val <= area_max;
 
         let num_intersect = storm
             .iter_pairs()
             .filter_map(|(a, b)| a.xy_intersection(&b))
             .filter(|intersection| {
-                test_area.contains(&intersection.x())
-                    && test_area.contains(&intersection.y())
+                in_test_area(intersection.x()) && in_test_area(intersection.y())
             })
             .count();
         Ok(num_intersect)
@@ -476,14 +255,14 @@ impl Puzzle for ThisDay {
             .into()
         };
 
-        let system: AugmentedMatrix<6, 5, i128> = {
+        let system: LinearSystem<6, 5, i128> = {
             let p0 = storm.hail[0].position;
             let p1 = storm.hail[1].position;
             let dv0 = v_rock - storm.hail[0].velocity;
             let dv1 = v_rock - storm.hail[1].velocity;
 
-            AugmentedMatrix {
-                matrix: Matrix::new([
+            LinearSystem::new(
+                Matrix::new([
                     [1, 0, 0, dv0.x(), 0],
                     [0, 1, 0, dv0.y(), 0],
                     [0, 0, 1, dv0.z(), 0],
@@ -491,15 +270,13 @@ impl Puzzle for ThisDay {
                     [0, 1, 0, 0, dv1.y()],
                     [0, 0, 1, 0, dv1.z()],
                 ]),
-                augment: [p0.x(), p0.y(), p0.z(), p1.x(), p1.y(), p1.z()]
-                    .into(),
-            }
+                [p0.x(), p0.y(), p0.z(), p1.x(), p1.y(), p1.z()].into(),
+            )
         };
 
-        let solution = system.solve_system();
+        let solution = system.solve().expect("No solution for position");
         let p_rock: Vector<3, i128> = solution
-            .expect("No solution for position")
-            .offset
+            .offset()
             .into_iter()
             .take(3)
             .map(|f| {